@@ -0,0 +1,175 @@
+use crate::solution::Answer;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+
+/// Known answers and rejected guesses for a single part
+#[derive(Debug, Default, Deserialize)]
+pub struct ExpectedAnswer {
+    pub answer: Option<String>,
+    #[serde(default)]
+    pub too_high: Vec<String>,
+    #[serde(default)]
+    pub too_low: Vec<String>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Verdict {
+    Pass,
+    Fail(String),
+    TooHigh,
+    TooLow,
+    Unknown,
+    Unsolved,
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Pass => f.write_str("pass"),
+            Verdict::Fail(expected) => write!(f, "FAIL (expected {expected})"),
+            Verdict::TooHigh => f.write_str("FAIL (too high)"),
+            Verdict::TooLow => f.write_str("FAIL (too low)"),
+            Verdict::Unknown => f.write_str("unknown"),
+            Verdict::Unsolved => f.write_str("unsolved"),
+        }
+    }
+}
+
+impl ExpectedAnswer {
+    pub fn verify(&self, answer: &Answer) -> Verdict {
+        if *answer == Answer::Unsolved {
+            return Verdict::Unsolved;
+        }
+
+        let answer = answer.to_string();
+
+        if let Some(expected) = &self.answer {
+            return if *expected == answer {
+                Verdict::Pass
+            } else {
+                Verdict::Fail(expected.clone())
+            };
+        }
+
+        // Anything past a rejected guess is just as wrong as the guess itself
+        if let Ok(number) = answer.parse::<u128>() {
+            let numbers = |guesses: &[String]| {
+                guesses
+                    .iter()
+                    .filter_map(|guess| guess.parse::<u128>().ok())
+                    .collect::<Vec<u128>>()
+            };
+
+            if numbers(&self.too_high).iter().any(|guess| number >= *guess) {
+                return Verdict::TooHigh;
+            }
+
+            if numbers(&self.too_low).iter().any(|guess| number <= *guess) {
+                return Verdict::TooLow;
+            }
+        }
+
+        Verdict::Unknown
+    }
+}
+
+/// Expected answers keyed by year, day and part, e.g. `[2024.day9.part1]`
+#[derive(Debug, Default, Deserialize)]
+#[serde(transparent)]
+pub struct Answers(HashMap<String, HashMap<String, HashMap<String, ExpectedAnswer>>>);
+
+impl Answers {
+    pub fn parse(text: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(text)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|err| format!("Couldn't read {}: {err}", path.display()))?;
+
+        Self::parse(&text).map_err(|err| format!("Couldn't parse {}: {err}", path.display()))
+    }
+
+    pub fn get(&self, year: u16, day: u8, part: u8) -> Option<&ExpectedAnswer> {
+        self.0
+            .get(&year.to_string())?
+            .get(&format!("day{day}"))?
+            .get(&format!("part{part}"))
+    }
+
+    pub fn verify(&self, year: u16, day: u8, part: u8, answer: &Answer) -> Verdict {
+        match self.get(year, day, part) {
+            Some(expected) => expected.verify(answer),
+            None => ExpectedAnswer::default().verify(answer),
+        }
+    }
+}
+
+#[cfg(test)]
+mod answers_tests {
+    use crate::answers::{Answers, Verdict};
+    use crate::solution::Answer;
+
+    const ANSWERS: &str = r#"
+[2022.day5.part1]
+answer = "CMZ"
+
+[2024.day8.part1]
+too_high = ["483", "450"]
+
+[2024.day7.part1]
+too_low = ["25243429947"]
+"#;
+
+    #[test]
+    fn verifies_known_answers() {
+        let answers = Answers::parse(ANSWERS).unwrap();
+
+        assert_eq!(
+            answers.verify(2022, 5, 1, &Answer::Text("CMZ".to_string())),
+            Verdict::Pass
+        );
+        assert_eq!(
+            answers.verify(2022, 5, 1, &Answer::Text("MCD".to_string())),
+            Verdict::Fail("CMZ".to_string())
+        );
+    }
+
+    #[test]
+    fn flags_rejected_guesses() {
+        let answers = Answers::parse(ANSWERS).unwrap();
+
+        assert_eq!(
+            answers.verify(2024, 8, 1, &Answer::Number(483)),
+            Verdict::TooHigh
+        );
+        assert_eq!(
+            answers.verify(2024, 8, 1, &Answer::Number(460)),
+            Verdict::TooHigh
+        );
+        assert_eq!(
+            answers.verify(2024, 8, 1, &Answer::Number(379)),
+            Verdict::Unknown
+        );
+        assert_eq!(
+            answers.verify(2024, 7, 1, &Answer::Number(1459904816)),
+            Verdict::TooLow
+        );
+    }
+
+    #[test]
+    fn unknown_and_unsolved_parts() {
+        let answers = Answers::parse(ANSWERS).unwrap();
+
+        assert_eq!(
+            answers.verify(2023, 1, 1, &Answer::Number(1)),
+            Verdict::Unknown
+        );
+        assert_eq!(
+            answers.verify(2024, 7, 2, &Answer::Unsolved),
+            Verdict::Unsolved
+        );
+    }
+}
@@ -0,0 +1,345 @@
+use crate::error::ParseError;
+use crate::solution::{Answer, Solution};
+use serde::{Deserialize, Serialize};
+use std::hint::black_box;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Timings of one stage of a puzzle, e.g. parsing or part 2
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Measurement {
+    pub year: u16,
+    pub day: u8,
+    pub stage: String,
+    pub runs: usize,
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub p95_ns: u64,
+    pub input_bytes: usize,
+    /// Megabytes of input per second, based on the median
+    pub throughput_mb_s: f64,
+}
+
+impl Measurement {
+    fn new(solution: &dyn Solution, stage: &str, input: &str, mut samples: Vec<Duration>) -> Self {
+        samples.sort();
+
+        let nanos = |duration: Duration| duration.as_nanos() as u64;
+        let median = samples[samples.len() / 2];
+        let p95 = samples[(samples.len() * 95).div_ceil(100) - 1];
+
+        Self {
+            year: solution.year(),
+            day: solution.day(),
+            stage: stage.to_owned(),
+            runs: samples.len(),
+            min_ns: nanos(samples[0]),
+            median_ns: nanos(median),
+            p95_ns: nanos(p95),
+            input_bytes: input.len(),
+            throughput_mb_s: input.len() as f64 / median.as_secs_f64().max(f64::EPSILON) / 1e6,
+        }
+    }
+
+    pub fn min(&self) -> Duration {
+        Duration::from_nanos(self.min_ns)
+    }
+
+    pub fn median(&self) -> Duration {
+        Duration::from_nanos(self.median_ns)
+    }
+
+    pub fn p95(&self) -> Duration {
+        Duration::from_nanos(self.p95_ns)
+    }
+}
+
+/// The first call isn't timed, it warms up caches and reports parse errors before measuring
+fn measure<T>(runs: usize, mut stage: impl FnMut() -> T) -> Vec<Duration> {
+    black_box(stage());
+
+    (0..runs.max(1))
+        .map(|_| {
+            let current_time = Instant::now();
+            black_box(stage());
+            current_time.elapsed()
+        })
+        .collect()
+}
+
+/// Runs the parser, both parts and every variant `runs` times each, unsolved parts are left out
+pub fn bench_solution(
+    solution: &dyn Solution,
+    input: &str,
+    runs: usize,
+) -> Result<Vec<Measurement>, ParseError> {
+    let input = black_box(input);
+    let mut measurements = Vec::new();
+
+    if let Some(result) = solution.parse(input) {
+        result.map_err(|err| err.in_puzzle(solution.year(), solution.day()))?;
+
+        let samples = measure(runs, || solution.parse(input));
+        measurements.push(Measurement::new(solution, "parse", input, samples));
+    }
+
+    for part in 1..=2 {
+        if solution.part(part, input).unwrap()? == Answer::Unsolved {
+            continue;
+        }
+
+        let samples = measure(runs, || solution.part(part, input));
+        measurements.push(Measurement::new(
+            solution,
+            &format!("part{part}"),
+            input,
+            samples,
+        ));
+    }
+
+    for variant in solution.variants() {
+        (variant.solve)(input).map_err(|err| err.in_puzzle(solution.year(), solution.day()))?;
+
+        let samples = measure(runs, || (variant.solve)(input));
+        measurements.push(Measurement::new(
+            solution,
+            &format!("part{}-{}", variant.part, variant.name),
+            input,
+            samples,
+        ));
+    }
+
+    Ok(measurements)
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ReportFormat {
+    Json,
+    Csv,
+}
+
+impl ReportFormat {
+    /// `.csv` files get CSV, everything else JSON
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("csv") => ReportFormat::Csv,
+            _ => ReportFormat::Json,
+        }
+    }
+}
+
+const CSV_HEADER: &str = "year,day,stage,runs,min_ns,median_ns,p95_ns,input_bytes,throughput_mb_s";
+
+pub fn to_csv(measurements: &[Measurement]) -> String {
+    let mut csv = format!("{CSV_HEADER}\n");
+
+    for m in measurements {
+        csv.push_str(&format!(
+            "{},{},{},{},{},{},{},{},{:.3}\n",
+            m.year,
+            m.day,
+            m.stage,
+            m.runs,
+            m.min_ns,
+            m.median_ns,
+            m.p95_ns,
+            m.input_bytes,
+            m.throughput_mb_s
+        ));
+    }
+
+    csv
+}
+
+pub fn to_json(measurements: &[Measurement]) -> String {
+    serde_json::to_string_pretty(measurements).unwrap()
+}
+
+pub fn write_report(path: &Path, measurements: &[Measurement]) -> Result<(), String> {
+    let report = match ReportFormat::from_path(path) {
+        ReportFormat::Json => to_json(measurements),
+        ReportFormat::Csv => to_csv(measurements),
+    };
+
+    std::fs::write(path, report).map_err(|err| format!("Couldn't write {}: {err}", path.display()))
+}
+
+/// Where `--save-baseline <name>` stores its timings, they only make sense on the machine that made them
+pub fn baseline_path(directory: &Path, name: &str) -> PathBuf {
+    directory.join(format!("{name}.json"))
+}
+
+pub fn save_baseline(path: &Path, measurements: &[Measurement]) -> Result<(), String> {
+    if let Some(directory) = path.parent() {
+        std::fs::create_dir_all(directory)
+            .map_err(|err| format!("Couldn't create {}: {err}", directory.display()))?;
+    }
+
+    std::fs::write(path, to_json(measurements))
+        .map_err(|err| format!("Couldn't write {}: {err}", path.display()))
+}
+
+pub fn load_baseline(path: &Path) -> Result<Vec<Measurement>, String> {
+    let text = std::fs::read_to_string(path)
+        .map_err(|err| format!("Couldn't read {}: {err}", path.display()))?;
+
+    serde_json::from_str(&text).map_err(|err| format!("Couldn't parse {}: {err}", path.display()))
+}
+
+/// A measurement next to the same stage of the baseline, if the baseline has it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison<'a> {
+    pub current: &'a Measurement,
+    pub baseline: Option<&'a Measurement>,
+}
+
+impl Comparison<'_> {
+    /// Change of the median in percent, positive is slower
+    pub fn change(&self) -> Option<f64> {
+        let baseline = self.baseline?.median_ns.max(1) as f64;
+
+        Some((self.current.median_ns as f64 - baseline) / baseline * 100.0)
+    }
+
+    pub fn regressed(&self, threshold: f64) -> bool {
+        self.change().is_some_and(|change| change > threshold)
+    }
+}
+
+pub fn compare<'a>(current: &'a [Measurement], baseline: &'a [Measurement]) -> Vec<Comparison<'a>> {
+    current
+        .iter()
+        .map(|m| Comparison {
+            current: m,
+            baseline: baseline
+                .iter()
+                .find(|b| (b.year, b.day, &b.stage) == (m.year, m.day, &m.stage)),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod bench_tests {
+    use crate::bench::{bench_solution, compare, to_csv, to_json, Measurement, ReportFormat};
+    use crate::error::ParseError;
+    use crate::solution::{Answer, Solution};
+    use std::path::Path;
+
+    fn measurements() -> Vec<Measurement> {
+        let solution = crate::find_solution(2022, 1).unwrap();
+
+        bench_solution(solution, "1000\n2000\n\n3000\n\n4000\n", 5).unwrap()
+    }
+
+    #[test]
+    fn measures_parse_and_parts() {
+        let measurements = measurements();
+
+        let stages: Vec<&str> = measurements.iter().map(|m| m.stage.as_str()).collect();
+        assert_eq!(
+            stages,
+            ["parse", "part1", "part2", "part1-faster", "part2-faster"]
+        );
+
+        for m in &measurements {
+            assert_eq!(m.runs, 5);
+            assert!(m.min() <= m.median() && m.median() <= m.p95());
+        }
+    }
+
+    #[test]
+    fn every_solution_has_a_parse_stage() {
+        for solution in crate::registry() {
+            assert!(
+                solution.parse("").is_some(),
+                "{} day {} has no parse stage",
+                solution.year(),
+                solution.day()
+            );
+        }
+    }
+
+    /// Only solves part 2
+    struct HalfSolved;
+
+    impl Solution for HalfSolved {
+        fn year(&self) -> u16 {
+            2024
+        }
+
+        fn day(&self) -> u8 {
+            1
+        }
+
+        fn title(&self) -> &'static str {
+            "Half solved"
+        }
+
+        fn part1(&self, _input: &str) -> Result<Answer, ParseError> {
+            Ok(Answer::Unsolved)
+        }
+
+        fn part2(&self, input: &str) -> Result<Answer, ParseError> {
+            Ok(input.len().into())
+        }
+    }
+
+    #[test]
+    fn skips_unsolved_parts() {
+        let measurements = bench_solution(&HalfSolved, "1 2 3\n", 1).unwrap();
+
+        assert_eq!(measurements.len(), 1);
+        assert_eq!(measurements[0].stage, "part2");
+    }
+
+    #[test]
+    fn reports_parse_errors() {
+        let solution = crate::find_solution(2022, 8).unwrap();
+
+        let error = bench_solution(solution, "123\n12\n", 1).unwrap_err();
+
+        assert_eq!(error.puzzle, Some((2022, 8)));
+    }
+
+    #[test]
+    fn writes_csv_and_json() {
+        let measurements = measurements();
+
+        let csv = to_csv(&measurements);
+        assert_eq!(csv.lines().count(), 6);
+        assert!(csv.lines().nth(2).unwrap().starts_with("2022,1,part1,5,"));
+
+        let json: Vec<Measurement> = serde_json::from_str(&to_json(&measurements)).unwrap();
+        assert_eq!(json, measurements);
+
+        assert_eq!(
+            ReportFormat::from_path(Path::new("bench.csv")),
+            ReportFormat::Csv
+        );
+        assert_eq!(
+            ReportFormat::from_path(Path::new("bench.json")),
+            ReportFormat::Json
+        );
+    }
+
+    #[test]
+    fn flags_regressions_past_threshold() {
+        let mut baseline = measurements();
+        baseline[0].median_ns = 1000;
+        baseline[1].median_ns = 1000;
+
+        let mut current = baseline.clone();
+        current[0].median_ns = 2000;
+        current[1].median_ns = 1050;
+        current[2].stage = "part3".to_string();
+
+        let comparisons = compare(&current, &baseline);
+
+        assert_eq!(comparisons[0].change(), Some(100.0));
+        assert!(comparisons[0].regressed(10.0));
+        assert!(!comparisons[1].regressed(10.0));
+        assert!(comparisons[1].regressed(1.0));
+        assert_eq!(comparisons[2].baseline, None);
+        assert!(!comparisons[2].regressed(0.0));
+    }
+}
@@ -0,0 +1,295 @@
+use crate::error::ParseErrorKind;
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, Mul};
+use std::str::FromStr;
+
+/// Each limb holds nine decimal digits, which keeps printing and parsing simple
+const BASE: u64 = 1_000_000_000;
+const BASE_DIGITS: usize = 9;
+
+/// Unsigned integer without an upper limit, for answers that don't fit in a `u128`
+///
+/// Limbs are stored least significant first, zero has no limbs at all.
+#[derive(Debug, Default, Clone, Eq, PartialEq, Hash)]
+pub struct BigUint {
+    limbs: Vec<u32>,
+}
+
+impl BigUint {
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    fn normalized(mut self) -> Self {
+        while self.limbs.last() == Some(&0) {
+            self.limbs.pop();
+        }
+
+        self
+    }
+
+    /// `None` when `other` is larger, the result can't go below zero
+    pub fn checked_sub(&self, other: &BigUint) -> Option<BigUint> {
+        if *self < *other {
+            return None;
+        }
+
+        let mut limbs = Vec::with_capacity(self.limbs.len());
+        let mut borrow = 0;
+
+        for (index, &limb) in self.limbs.iter().enumerate() {
+            let subtrahend = u64::from(other.limbs.get(index).copied().unwrap_or(0)) + borrow;
+            let limb = u64::from(limb);
+
+            if limb >= subtrahend {
+                limbs.push((limb - subtrahend) as u32);
+                borrow = 0;
+            } else {
+                limbs.push((limb + BASE - subtrahend) as u32);
+                borrow = 1;
+            }
+        }
+
+        Some(BigUint { limbs }.normalized())
+    }
+
+    fn mul_small(&self, factor: u32) -> BigUint {
+        let mut limbs = Vec::with_capacity(self.limbs.len() + 1);
+        let mut carry = 0;
+
+        for &limb in &self.limbs {
+            let product = u64::from(limb) * u64::from(factor) + carry;
+            limbs.push((product % BASE) as u32);
+            carry = product / BASE;
+        }
+        if carry > 0 {
+            limbs.push(carry as u32);
+        }
+
+        BigUint { limbs }.normalized()
+    }
+
+    /// Quotient and remainder, `None` when dividing by zero
+    pub fn div_rem(&self, divisor: &BigUint) -> Option<(BigUint, BigUint)> {
+        if divisor.is_zero() {
+            return None;
+        }
+
+        let mut quotient = vec![0; self.limbs.len()];
+        let mut remainder = BigUint::default();
+
+        // Long division, one limb at a time, the digit is found by bisecting the base
+        for (index, &limb) in self.limbs.iter().enumerate().rev() {
+            remainder.limbs.insert(0, limb);
+            remainder = remainder.normalized();
+
+            let (mut low, mut high) = (0, BASE as u32 - 1);
+            while low < high {
+                let middle = low + (high - low).div_ceil(2);
+
+                if divisor.mul_small(middle) <= remainder {
+                    low = middle;
+                } else {
+                    high = middle - 1;
+                }
+            }
+
+            quotient[index] = low;
+            remainder = remainder.checked_sub(&divisor.mul_small(low)).unwrap();
+        }
+
+        Some((BigUint { limbs: quotient }.normalized(), remainder))
+    }
+
+    /// `None` when the number doesn't fit
+    pub fn to_u64(&self) -> Option<u64> {
+        self.limbs.iter().rev().try_fold(0u64, |number, &limb| {
+            number.checked_mul(BASE)?.checked_add(u64::from(limb))
+        })
+    }
+}
+
+impl From<u64> for BigUint {
+    fn from(mut number: u64) -> Self {
+        let mut limbs = Vec::new();
+
+        while number > 0 {
+            limbs.push((number % BASE) as u32);
+            number /= BASE;
+        }
+
+        BigUint { limbs }
+    }
+}
+
+/// Only decimal digits, no sign
+impl FromStr for BigUint {
+    type Err = ParseErrorKind;
+
+    fn from_str(text: &str) -> Result<Self, ParseErrorKind> {
+        if text.is_empty() || !text.bytes().all(|byte| byte.is_ascii_digit()) {
+            return Err(ParseErrorKind::ExpectedInteger);
+        }
+
+        let limbs = text
+            .as_bytes()
+            .rchunks(BASE_DIGITS)
+            .map(|chunk| {
+                chunk
+                    .iter()
+                    .fold(0, |limb, digit| limb * 10 + u32::from(digit - b'0'))
+            })
+            .collect();
+
+        Ok(BigUint { limbs }.normalized())
+    }
+}
+
+impl Display for BigUint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let Some((most_significant, rest)) = self.limbs.split_last() else {
+            return f.write_str("0");
+        };
+
+        write!(f, "{most_significant}")?;
+        for limb in rest.iter().rev() {
+            write!(f, "{limb:0BASE_DIGITS$}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.limbs
+            .len()
+            .cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Add for &BigUint {
+    type Output = BigUint;
+
+    fn add(self, other: &BigUint) -> BigUint {
+        let length = self.limbs.len().max(other.limbs.len());
+        let mut limbs = Vec::with_capacity(length + 1);
+        let mut carry = 0;
+
+        for index in 0..length {
+            let sum = u64::from(self.limbs.get(index).copied().unwrap_or(0))
+                + u64::from(other.limbs.get(index).copied().unwrap_or(0))
+                + carry;
+            limbs.push((sum % BASE) as u32);
+            carry = sum / BASE;
+        }
+        if carry > 0 {
+            limbs.push(carry as u32);
+        }
+
+        BigUint { limbs }
+    }
+}
+
+impl Mul for &BigUint {
+    type Output = BigUint;
+
+    fn mul(self, other: &BigUint) -> BigUint {
+        if self.is_zero() || other.is_zero() {
+            return BigUint::default();
+        }
+
+        let mut products = vec![0u64; self.limbs.len() + other.limbs.len()];
+
+        for (i, &a) in self.limbs.iter().enumerate() {
+            let mut carry = 0;
+
+            for (j, &b) in other.limbs.iter().enumerate() {
+                let product = products[i + j] + u64::from(a) * u64::from(b) + carry;
+                products[i + j] = product % BASE;
+                carry = product / BASE;
+            }
+            products[i + other.limbs.len()] += carry;
+        }
+
+        let limbs = products.into_iter().map(|limb| limb as u32).collect();
+        BigUint { limbs }.normalized()
+    }
+}
+
+#[cfg(test)]
+mod bigint_tests {
+    use crate::bigint::BigUint;
+    use crate::error::ParseErrorKind;
+
+    fn big(text: &str) -> BigUint {
+        text.parse().unwrap()
+    }
+
+    #[test]
+    fn parses_and_displays() {
+        assert_eq!(big("0").to_string(), "0");
+        assert_eq!(big("000123").to_string(), "123");
+        assert_eq!(
+            big("123456789012345678901234567890").to_string(),
+            "123456789012345678901234567890"
+        );
+        assert_eq!(big("1000000000").to_string(), "1000000000");
+        assert_eq!(BigUint::from(u64::MAX).to_string(), u64::MAX.to_string());
+        assert_eq!(
+            "12a".parse::<BigUint>(),
+            Err(ParseErrorKind::ExpectedInteger)
+        );
+        assert_eq!("".parse::<BigUint>(), Err(ParseErrorKind::ExpectedInteger));
+    }
+
+    #[test]
+    fn matches_u128_arithmetic() {
+        let numbers: [u128; 5] = [0, 7, 999_999_999, 1_000_000_000, u64::MAX as u128];
+
+        for a in numbers {
+            for b in numbers {
+                let (big_a, big_b) = (big(&a.to_string()), big(&b.to_string()));
+
+                assert_eq!((&big_a + &big_b).to_string(), (a + b).to_string());
+                assert_eq!((&big_a * &big_b).to_string(), (a * b).to_string());
+                assert_eq!(
+                    big_a.checked_sub(&big_b).map(|n| n.to_string()),
+                    a.checked_sub(b).map(|n| n.to_string())
+                );
+                assert_eq!(big_a.cmp(&big_b), a.cmp(&b));
+
+                let div_rem = big_a.div_rem(&big_b);
+                let expected = (b != 0).then(|| ((a / b).to_string(), (a % b).to_string()));
+                assert_eq!(
+                    div_rem.map(|(q, r)| (q.to_string(), r.to_string())),
+                    expected
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn divides_long_numbers() {
+        let a = big("123456789012345678901234567890");
+        let b = big("9876543210987");
+
+        let (quotient, remainder) = (&a * &b).div_rem(&b).unwrap();
+        assert_eq!((quotient, remainder), (a.clone(), BigUint::default()));
+
+        let (quotient, remainder) = a.div_rem(&b).unwrap();
+        assert_eq!(&(&quotient * &b) + &remainder, a);
+        assert!(remainder < b);
+
+        assert_eq!(big("18446744073709551615").to_u64(), Some(u64::MAX));
+        assert_eq!(big("18446744073709551616").to_u64(), None);
+    }
+}
@@ -1,3 +1,6 @@
+use crate::day1_faster;
+use crate::error::{parse_integer, ParseError, ParseErrorKind};
+use crate::solution::{Answer, Solution, Variant};
 use std::cmp::Ordering;
 
 struct ElfLists(Vec<ElfList>);
@@ -45,21 +48,29 @@ fn sort_elf_lists(mut elf_lists: ElfLists) -> SortedElfLists {
     SortedElfLists(elf_lists.0)
 }
 
-fn get_day_calories_for_elf(calorie_lists: &SortedElfLists, rank: usize) -> usize {
-    calorie_lists
-        .0
-        .get(rank)
-        .expect("Elf list not found")
-        .get_total_calories()
+fn get_day_calories_for_elf(
+    input: &str,
+    calorie_lists: &SortedElfLists,
+    rank: usize,
+) -> Result<usize, ParseError> {
+    let elf_list = calorie_lists.0.get(rank).ok_or_else(|| {
+        ParseError::new(
+            input,
+            &input[input.len()..],
+            ParseErrorKind::Invalid("not enough elves"),
+        )
+    })?;
+
+    Ok(elf_list.get_total_calories())
 }
 
-fn get_top_3(calorie_lists: &SortedElfLists) -> usize {
-    get_day_calories_for_elf(calorie_lists, 0)
-        + get_day_calories_for_elf(calorie_lists, 1)
-        + get_day_calories_for_elf(calorie_lists, 2)
+fn get_top_3(input: &str, calorie_lists: &SortedElfLists) -> Result<usize, ParseError> {
+    Ok(get_day_calories_for_elf(input, calorie_lists, 0)?
+        + get_day_calories_for_elf(input, calorie_lists, 1)?
+        + get_day_calories_for_elf(input, calorie_lists, 2)?)
 }
 
-fn parse_input(input: &str) -> ElfLists {
+fn parse_input(input: &str) -> Result<ElfLists, ParseError> {
     let lines: Vec<&str> = input.split('\n').collect();
 
     let mut result = Vec::new();
@@ -72,37 +83,115 @@ fn parse_input(input: &str) -> ElfLists {
             continue;
         }
 
-        current_list
-            .calories
-            .push(line.parse::<usize>().expect("Incorrect data"));
+        current_list.calories.push(parse_integer(input, line)?);
     }
 
-    ElfLists(result)
+    Ok(ElfLists(result))
+}
+
+pub fn challenge_1(input: &str) -> Result<usize, ParseError> {
+    let sorted_elf_lists = sort_elf_lists(parse_input(input)?);
+
+    get_day_calories_for_elf(input, &sorted_elf_lists, 0)
+}
+
+pub fn challenge_2(input: &str) -> Result<usize, ParseError> {
+    let sorted_elf_lists = sort_elf_lists(parse_input(input)?);
+
+    get_top_3(input, &sorted_elf_lists)
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    fn year(&self) -> u16 {
+        2022
+    }
+
+    fn day(&self) -> u8 {
+        1
+    }
+
+    fn title(&self) -> &'static str {
+        "Calorie Counting"
+    }
+
+    fn parse(&self, input: &str) -> Option<Result<(), ParseError>> {
+        Some(parse_input(input).map(drop))
+    }
+
+    fn variants(&self) -> &'static [Variant] {
+        &[
+            Variant {
+                name: "faster",
+                part: 1,
+                solve: day1_faster::challenge_1,
+            },
+            Variant {
+                name: "faster",
+                part: 2,
+                solve: day1_faster::challenge_2,
+            },
+        ]
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, ParseError> {
+        Ok(challenge_1(input)?.into())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, ParseError> {
+        Ok(challenge_2(input)?.into())
+    }
 }
 
 #[cfg(test)]
 mod day1_test {
-    use crate::day1::{get_day_calories_for_elf, get_top_3, parse_input, sort_elf_lists};
+    use crate::day1;
+    use crate::day1::{get_day_calories_for_elf, get_top_3, parse_input, sort_elf_lists, Day1};
+    use crate::input::puzzle_input;
+    use crate::solution::Solution;
 
     #[test]
     fn challenge_1() {
-        let input = include_str!("input.txt");
-        let elf_lists = parse_input(input);
+        let input = &puzzle_input(2022, 1);
+        let elf_lists = parse_input(input).unwrap();
 
         let sorted_elf_lists = sort_elf_lists(elf_lists);
-        let sum_calories = get_day_calories_for_elf(&sorted_elf_lists, 0);
+        let sum_calories = get_day_calories_for_elf(input, &sorted_elf_lists, 0).unwrap();
 
         assert_eq!(sum_calories, 70720);
     }
 
     #[test]
     fn challenge_2() {
-        let input = include_str!("input.txt");
-        let elf_lists = parse_input(input);
+        let input = &puzzle_input(2022, 1);
+        let elf_lists = parse_input(input).unwrap();
 
         let sorted_elf_lists = sort_elf_lists(elf_lists);
-        let sum_calories = get_top_3(&sorted_elf_lists);
+        let sum_calories = get_top_3(input, &sorted_elf_lists).unwrap();
 
         println!("{}", sum_calories);
     }
+
+    #[test]
+    fn faster_variants_agree() {
+        let input = &puzzle_input(2022, 1);
+
+        for variant in Day1.variants() {
+            assert_eq!(
+                (variant.solve)(input),
+                Day1.part(variant.part, input).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn reports_too_few_elves() {
+        let error = day1::challenge_2("1000\n2000\n\n3000\n").unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "line 5, column 1: not enough elves, found ''"
+        );
+    }
 }
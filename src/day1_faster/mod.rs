@@ -1,40 +1,42 @@
-#[no_mangle]
-fn parse_input(str: &str) -> Vec<usize> {
+use crate::error::{parse_integer, ParseError, ParseErrorKind};
+use crate::solution::Answer;
+
+fn parse_input(str: &str) -> Result<Vec<usize>, ParseError> {
     let mut result: Vec<usize> = str
         .split("\n\n")
         .map(|elf_calorie_lines: &str| {
             elf_calorie_lines
                 .split('\n')
-                .filter_map(|calories: &str| match calories.is_empty() {
-                    true => None,
-                    false => Some(calories.parse::<usize>().unwrap()),
-                })
+                .filter(|calories: &&str| !calories.is_empty())
+                .map(|calories: &str| parse_integer::<usize>(str, calories))
                 .sum()
         })
-        .collect::<Vec<usize>>();
+        .collect::<Result<Vec<usize>, ParseError>>()?;
 
     result.sort();
     result.reverse();
 
-    result
+    Ok(result)
 }
 
-#[cfg(test)]
-mod day1_test_performance {
-    use crate::day1_faster::parse_input;
-    use benchmark_simple::{Bench, Options};
-
-    #[test]
-    fn get_top_3() {
-        let bench = Bench::new();
-        let options = Options::default();
-        let res = bench.run(&options, run);
-        println!("result: {} nano seconds", res.as_ns());
-    }
+fn top_calories(input: &str, elves: usize) -> Result<usize, ParseError> {
+    let calories = parse_input(input)?;
 
-    #[no_mangle]
-    fn run() {
-        let input = include_str!("input.txt");
-        parse_input(input);
+    if calories.len() < elves {
+        return Err(ParseError::new(
+            input,
+            &input[input.len()..],
+            ParseErrorKind::Invalid("not enough elves"),
+        ));
     }
+
+    Ok(calories[..elves].iter().sum())
+}
+
+pub fn challenge_1(input: &str) -> Result<Answer, ParseError> {
+    Ok(top_calories(input, 1)?.into())
+}
+
+pub fn challenge_2(input: &str) -> Result<Answer, ParseError> {
+    Ok(top_calories(input, 3)?.into())
 }
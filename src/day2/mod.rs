@@ -1,148 +1,202 @@
-fn calculate_points(them: &str, us: &str) -> usize {
-    match them {
-        "A" => match us {
-            "X" => 3,
-            "Y" => 6,
-            _ => 0,
-        },
-        "B" => match us {
-            "X" => 0,
-            "Y" => 3,
-            _ => 6,
-        },
-        "C" => match us {
-            "X" => 6,
-            "Y" => 0,
-            _ => 3,
-        },
-        _ => unimplemented!(),
-    }
+use crate::error::{expect_field, ParseError, ParseErrorKind};
+use crate::solution::{Answer, Solution};
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum Shape {
+    Rock,
+    Paper,
+    Scissors,
 }
 
-fn parse_input_challenge_1(str: &str) -> usize {
-    str.split('\n')
-        .filter_map(|line| {
-            if line.is_empty() {
-                return None;
-            }
-            let mut splitted = line.split(' ').clone();
-            let them = splitted.next().unwrap();
-            let us = splitted.next().unwrap();
-
-            let match_points = calculate_points(them, us);
-
-            let our_points = match us {
-                "X" => 1,
-                "Y" => 2,
-                "Z" => 3,
-                _ => 0,
-            };
+impl Shape {
+    fn points(self) -> usize {
+        match self {
+            Shape::Rock => 1,
+            Shape::Paper => 2,
+            Shape::Scissors => 3,
+        }
+    }
 
-            Some(match_points + our_points)
-        })
-        .sum()
-}
+    fn beats(self) -> Shape {
+        match self {
+            Shape::Rock => Shape::Scissors,
+            Shape::Paper => Shape::Rock,
+            Shape::Scissors => Shape::Paper,
+        }
+    }
 
-fn get_winning_input(them: &str) -> &'static str {
-    match them {
-        "A" => "Y",
-        "B" => "Z",
-        "C" => "X",
-        _ => panic!(),
+    fn loses_to(self) -> Shape {
+        match self {
+            Shape::Rock => Shape::Paper,
+            Shape::Paper => Shape::Scissors,
+            Shape::Scissors => Shape::Rock,
+        }
     }
 }
 
-fn get_losing_input(them: &str) -> &'static str {
-    match them {
-        "A" => "Z",
-        "B" => "X",
-        "C" => "Y",
-        _ => panic!(),
-    }
+/// The second column, a shape to play in challenge 1 and an outcome in challenge 2
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum Response {
+    X,
+    Y,
+    Z,
 }
 
-fn get_matching_input(them: &str) -> &'static str {
-    match them {
-        "A" => "X",
-        "B" => "Y",
-        "C" => "Z",
-        _ => panic!(),
+fn calculate_points(them: Shape, us: Shape) -> usize {
+    if us.beats() == them {
+        6
+    } else if us == them {
+        3
+    } else {
+        0
     }
 }
 
-fn get_input_points(us: &str) -> usize {
-    match us {
-        "X" => 1,
-        "Y" => 2,
-        "Z" => 3,
-        _ => unimplemented!(),
-    }
+fn parse_round(input: &str, line: &str) -> Result<(Shape, Response), ParseError> {
+    let mut splitted = line.split(' ');
+    let them = expect_field(input, line, splitted.next(), "opponent shape")?;
+    let us = expect_field(input, line, splitted.next(), "response")?;
+
+    let them = match them {
+        "A" => Shape::Rock,
+        "B" => Shape::Paper,
+        "C" => Shape::Scissors,
+        _ => {
+            return Err(ParseError::new(
+                input,
+                them,
+                ParseErrorKind::UnknownToken("opponent shape"),
+            ))
+        }
+    };
+
+    let us = match us {
+        "X" => Response::X,
+        "Y" => Response::Y,
+        "Z" => Response::Z,
+        _ => {
+            return Err(ParseError::new(
+                input,
+                us,
+                ParseErrorKind::UnknownToken("response"),
+            ))
+        }
+    };
+
+    Ok((them, us))
 }
 
-fn parse_input_challenge_2(str: &str) -> usize {
-    str.split('\n')
-        .filter_map(|line| {
-            if line.is_empty() {
-                return None;
-            }
-            let mut splitted = line.split(' ').clone();
-            let them = splitted.next().unwrap();
-            let us = splitted.next().unwrap();
+fn parse_rounds(input: &str) -> Result<Vec<(Shape, Response)>, ParseError> {
+    input
+        .split('\n')
+        .filter(|line| !line.is_empty())
+        .map(|line| parse_round(input, line))
+        .collect()
+}
 
-            let (match_points, input) = match us {
-                "X" => (0, get_losing_input(them)),
-                "Y" => (3, get_matching_input(them)),
-                "Z" => (6, get_winning_input(them)),
-                _ => panic!(),
+pub fn parse_input_challenge_1(str: &str) -> Result<usize, ParseError> {
+    Ok(parse_rounds(str)?
+        .into_iter()
+        .map(|(them, us)| {
+            let us = match us {
+                Response::X => Shape::Rock,
+                Response::Y => Shape::Paper,
+                Response::Z => Shape::Scissors,
             };
 
-            let input_points = get_input_points(input);
+            calculate_points(them, us) + us.points()
+        })
+        .sum())
+}
+
+pub fn parse_input_challenge_2(str: &str) -> Result<usize, ParseError> {
+    Ok(parse_rounds(str)?
+        .into_iter()
+        .map(|(them, us)| {
+            let (match_points, input) = match us {
+                Response::X => (0, them.beats()),
+                Response::Y => (3, them),
+                Response::Z => (6, them.loses_to()),
+            };
 
-            Some(match_points + input_points)
+            match_points + input.points()
         })
-        .sum()
+        .sum())
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    fn year(&self) -> u16 {
+        2022
+    }
+
+    fn day(&self) -> u8 {
+        2
+    }
+
+    fn title(&self) -> &'static str {
+        "Rock Paper Scissors"
+    }
+
+    fn fixtures(&self) -> &'static [(&'static str, &'static str)] {
+        &[("example", include_str!("test_input.txt"))]
+    }
+
+    fn parse(&self, input: &str) -> Option<Result<(), ParseError>> {
+        Some(parse_rounds(input).map(drop))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, ParseError> {
+        Ok(parse_input_challenge_1(input)?.into())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, ParseError> {
+        Ok(parse_input_challenge_2(input)?.into())
+    }
 }
 
 #[cfg(test)]
 mod test {
-    use crate::day2::{calculate_points, parse_input_challenge_1, parse_input_challenge_2};
+    use crate::day2::{calculate_points, parse_input_challenge_1, parse_input_challenge_2, Shape};
+    use crate::input::puzzle_input;
 
     #[test]
     fn test_match_points() {
-        assert_eq!(calculate_points("A", "X"), 3);
-        assert_eq!(calculate_points("A", "Y"), 6);
-        assert_eq!(calculate_points("A", "Z"), 0);
+        assert_eq!(calculate_points(Shape::Rock, Shape::Rock), 3);
+        assert_eq!(calculate_points(Shape::Rock, Shape::Paper), 6);
+        assert_eq!(calculate_points(Shape::Rock, Shape::Scissors), 0);
     }
 
     #[test]
     fn challenge_1_test() {
         let input = include_str!("test_input.txt");
 
-        let result = parse_input_challenge_1(input);
+        let result = parse_input_challenge_1(input).unwrap();
         assert_eq!(result, 15);
     }
 
     #[test]
     fn challenge_2_test() {
-        let input = include_str!("input.txt");
+        let input = &puzzle_input(2022, 2);
 
-        let result = parse_input_challenge_2(input);
+        let result = parse_input_challenge_2(input).unwrap();
         assert_eq!(result, 15442);
     }
 
     #[test]
     fn challenge_1() {
-        let input = include_str!("input.txt");
+        let input = &puzzle_input(2022, 2);
 
-        let result = parse_input_challenge_1(input);
+        let result = parse_input_challenge_1(input).unwrap();
         println!("{}", result);
     }
 
     #[test]
     fn challenge_2() {
-        let input = include_str!("input.txt");
+        let input = &puzzle_input(2022, 2);
 
-        let result = parse_input_challenge_1(input);
+        let result = parse_input_challenge_1(input).unwrap();
         println!("{}", result);
     }
 }
@@ -1,7 +1,9 @@
-use std::collections::{HashMap, HashSet};
+use crate::error::{ParseError, ParseErrorKind};
+use crate::solution::{Answer, Solution};
+use std::collections::HashSet;
 
-fn find_matching_item(input: &[&str]) -> char {
-    let first = input.get(0).expect("No first item found");
+fn find_matching_item(input: &[&str]) -> Option<char> {
+    let first = input.get(0)?;
 
     let hashmaps: Vec<HashSet<char>> = input
         .iter()
@@ -19,14 +21,11 @@ fn find_matching_item(input: &[&str]) -> char {
         })
         .collect();
 
-    first
-        .chars()
-        .find(|chr| {
-            let not_in_some_hashmaps = hashmaps.iter().any(|hashmap| !hashmap.contains(&chr));
+    first.chars().find(|chr| {
+        let not_in_some_hashmaps = hashmaps.iter().any(|hashmap| !hashmap.contains(&chr));
 
-            !not_in_some_hashmaps
-        })
-        .expect(&format!("No matching character found {}", first))
+        !not_in_some_hashmaps
+    })
 }
 
 fn calculate_item_score(chr: char) -> u32 {
@@ -40,71 +39,120 @@ fn calculate_item_score(chr: char) -> u32 {
     result + 1
 }
 
-fn challenge1(input: &str) -> usize {
+fn parse_backpack<'a>(input: &str, backpack: &'a str) -> Result<&'a str, ParseError> {
+    match backpack.find(|chr: char| !chr.is_ascii_alphabetic()) {
+        None => Ok(backpack),
+        Some(index) => {
+            let chr_len = backpack[index..].chars().next().unwrap().len_utf8();
+
+            Err(ParseError::new(
+                input,
+                &backpack[index..index + chr_len],
+                ParseErrorKind::UnknownToken("item"),
+            ))
+        }
+    }
+}
+
+fn get_item_score(input: &str, backpacks: &[&str]) -> Result<usize, ParseError> {
+    let item = find_matching_item(backpacks).ok_or_else(|| {
+        ParseError::new(
+            input,
+            backpacks[0],
+            ParseErrorKind::Invalid("no item shared between backpacks"),
+        )
+    })?;
+
+    Ok(calculate_item_score(item) as usize)
+}
+
+fn parse_backpacks(input: &str) -> Result<Vec<&str>, ParseError> {
     input
         .split('\n')
-        .filter_map(|backpack| {
-            if backpack.is_empty() {
-                return None;
-            }
+        .filter(|backpack| !backpack.is_empty())
+        .map(|backpack| parse_backpack(input, backpack))
+        .collect()
+}
 
+pub fn challenge1(input: &str) -> Result<usize, ParseError> {
+    parse_backpacks(input)?
+        .into_iter()
+        .map(|backpack| {
             let comp1 = &backpack[0..backpack.len() / 2];
             let comp2 = &backpack[backpack.len() / 2..backpack.len()];
 
-            let item = find_matching_item(&[comp1, comp2]);
-            let score = calculate_item_score(item);
-
-            Some(score as usize)
+            get_item_score(input, &[comp1, comp2])
         })
         .sum()
 }
 
-fn challenge2(input: &str) -> usize {
-    input
-        .split('\n')
-        .map(|str| str.to_owned())
-        .collect::<Vec<String>>()
+pub fn challenge2(input: &str) -> Result<usize, ParseError> {
+    parse_backpacks(input)?
         .chunks(3)
-        .filter_map(|backpacks| {
-            if backpacks.is_empty() {
-                return None;
+        .map(|backpacks| {
+            if backpacks.len() < 3 {
+                let last = backpacks[backpacks.len() - 1];
+
+                return Err(ParseError::new(
+                    input,
+                    &last[last.len()..],
+                    ParseErrorKind::MissingField("backpack of the group"),
+                ));
             }
 
-            let backpack1 = backpacks.get(0).unwrap();
+            get_item_score(input, backpacks)
+        })
+        .sum()
+}
 
-            if backpack1.is_empty() {
-                return None;
-            }
-            let backpack2 = backpacks.get(1).unwrap();
-            let backpack3 = backpacks.get(2).unwrap();
+pub struct Day3;
 
-            println!(
-                "collection: {} \n{} \n{}\n\n",
-                backpack1, backpack2, backpack3
-            );
+impl Solution for Day3 {
+    fn year(&self) -> u16 {
+        2022
+    }
 
-            let item = find_matching_item(&[backpack1, backpack2, backpack3]);
+    fn day(&self) -> u8 {
+        3
+    }
 
-            Some(calculate_item_score(item) as usize)
-        })
-        .sum()
+    fn title(&self) -> &'static str {
+        "Rucksack Reorganization"
+    }
+
+    fn fixtures(&self) -> &'static [(&'static str, &'static str)] {
+        &[("example", include_str!("test_input.txt"))]
+    }
+
+    fn parse(&self, input: &str) -> Option<Result<(), ParseError>> {
+        Some(parse_backpacks(input).map(drop))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, ParseError> {
+        Ok(challenge1(input)?.into())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, ParseError> {
+        Ok(challenge2(input)?.into())
+    }
 }
 
 #[cfg(test)]
 mod test_day_3 {
     use crate::day3::{challenge1, challenge2};
+    use crate::input::puzzle_input;
 
     #[test]
     fn calculate_challenge1_test_input() {
         let input = include_str!("test_input.txt");
-        let sum = challenge1(input);
+        let sum = challenge1(input).unwrap();
         assert_eq!(sum, 157);
     }
 
     #[test]
     fn get_challenge1_output() {
-        let input = include_str!("input.txt");
-        let sum = challenge1(input);
+        let input = &puzzle_input(2022, 3);
+        let sum = challenge1(input).unwrap();
 
         println!("{}", sum)
     }
@@ -112,14 +160,14 @@ mod test_day_3 {
     #[test]
     fn calculate_challenge2_test_input() {
         let input = include_str!("test_input.txt");
-        let sum = challenge2(input);
+        let sum = challenge2(input).unwrap();
         assert_eq!(sum, 70);
     }
 
     #[test]
     fn calculate_challenge2() {
-        let input = include_str!("input.txt");
-        let sum = challenge2(input);
+        let input = &puzzle_input(2022, 3);
+        let sum = challenge2(input).unwrap();
 
         println!("{}", sum);
     }
@@ -1,13 +1,16 @@
+use crate::error::{expect_field, parse_integer, ParseError};
+use crate::solution::{Answer, Solution};
+
 struct CleanRange(usize, usize);
 
 impl CleanRange {
-    fn new(input: &str) -> Self {
-        let mut splitted = input.split('-');
+    fn new(input: &str, range: &str) -> Result<Self, ParseError> {
+        let mut splitted = range.split('-');
 
-        Self {
-            0: splitted.next().unwrap().parse::<usize>().unwrap(),
-            1: splitted.next().unwrap().parse::<usize>().unwrap(),
-        }
+        Ok(Self {
+            0: parse_integer(input, expect_field(input, range, splitted.next(), "start")?)?,
+            1: parse_integer(input, expect_field(input, range, splitted.next(), "end")?)?,
+        })
     }
 
     fn has_full_overlap(&self, other: &CleanRange) -> bool {
@@ -19,43 +22,80 @@ impl CleanRange {
     }
 }
 
-fn parse_input(input: &str, challenge_2: bool) -> usize {
+fn parse_pairs(input: &str) -> Result<Vec<(CleanRange, CleanRange)>, ParseError> {
     input
         .split('\n')
-        .filter_map(|line| {
-            if line.is_empty() {
-                return None;
-            }
-
+        .filter(|line| !line.is_empty())
+        .map(|line| {
             let mut splitted = line.split(',');
-            let range_1 = CleanRange::new(splitted.next().unwrap());
-            let range_2 = CleanRange::new(splitted.next().unwrap());
+            let range_1 = CleanRange::new(
+                input,
+                expect_field(input, line, splitted.next(), "first range")?,
+            )?;
+            let range_2 = CleanRange::new(
+                input,
+                expect_field(input, line, splitted.next(), "second range")?,
+            )?;
+
+            Ok((range_1, range_2))
+        })
+        .collect()
+}
 
+pub fn parse_input(input: &str, challenge_2: bool) -> Result<usize, ParseError> {
+    Ok(parse_pairs(input)?
+        .iter()
+        .filter(|(range_1, range_2)| {
             if !challenge_2 {
-                if range_1.has_full_overlap(&range_2) || range_2.has_full_overlap(&range_1) {
-                    Some(1)
-                } else {
-                    Some(0)
-                }
+                range_1.has_full_overlap(range_2) || range_2.has_full_overlap(range_1)
             } else {
-                if range_1.has_some_overlap(&range_2) || range_2.has_some_overlap(&range_1) {
-                    Some(1)
-                } else {
-                    Some(0)
-                }
+                range_1.has_some_overlap(range_2) || range_2.has_some_overlap(range_1)
             }
         })
-        .sum()
+        .count())
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    fn year(&self) -> u16 {
+        2022
+    }
+
+    fn day(&self) -> u8 {
+        4
+    }
+
+    fn title(&self) -> &'static str {
+        "Camp Cleanup"
+    }
+
+    fn fixtures(&self) -> &'static [(&'static str, &'static str)] {
+        &[("example", include_str!("test_input.txt"))]
+    }
+
+    fn parse(&self, input: &str) -> Option<Result<(), ParseError>> {
+        Some(parse_pairs(input).map(drop))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, ParseError> {
+        Ok(parse_input(input, false)?.into())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, ParseError> {
+        Ok(parse_input(input, true)?.into())
+    }
 }
 
 #[cfg(test)]
 mod day4_tests {
     use crate::day4::parse_input;
+    use crate::input::puzzle_input;
 
     #[test]
     fn challenge_1_test_input() {
         let input = include_str!("test_input.txt");
-        let result = parse_input(input, false);
+        let result = parse_input(input, false).unwrap();
 
         assert_eq!(result, 2);
     }
@@ -63,23 +103,23 @@ mod day4_tests {
     #[test]
     fn challenge_2_test_input() {
         let input = include_str!("test_input.txt");
-        let result = parse_input(input, true);
+        let result = parse_input(input, true).unwrap();
 
         assert_eq!(result, 4);
     }
 
     #[test]
     fn challenge_1_output() {
-        let input = include_str!("input.txt");
-        let result = parse_input(input, false);
+        let input = &puzzle_input(2022, 4);
+        let result = parse_input(input, false).unwrap();
 
         println!("{}", result);
     }
 
     #[test]
     fn challenge_2_output() {
-        let input = include_str!("input.txt");
-        let result = parse_input(input, true);
+        let input = &puzzle_input(2022, 4);
+        let result = parse_input(input, true).unwrap();
 
         println!("{}", result);
     }
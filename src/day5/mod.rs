@@ -1,3 +1,6 @@
+use crate::error::{expect_field, parse_integer, ParseError, ParseErrorKind};
+use crate::solution::{Answer, Solution};
+
 struct Dock {
     stack_count: usize,
     stacks: Vec<CrateStack>,
@@ -11,21 +14,16 @@ impl Dock {
         }
     }
 
-    fn pop(&mut self, stack: usize) -> char {
-        self.stacks
-            .get_mut(stack)
-            .expect("Stack not found")
-            .crates
-            .pop()
-            .expect("Empty stack")
+    /// `None` when the stack is empty or doesn't exist
+    fn pop(&mut self, stack: usize) -> Option<char> {
+        self.stacks.get_mut(stack)?.crates.pop()
     }
 
-    fn push(&mut self, stack: usize, chr: char) {
-        self.stacks
-            .get_mut(stack)
-            .expect("Stack not found")
-            .crates
-            .push(chr)
+    /// `None` when the stack doesn't exist
+    fn push(&mut self, stack: usize, chr: char) -> Option<()> {
+        self.stacks.get_mut(stack)?.crates.push(chr);
+
+        Some(())
     }
 }
 
@@ -36,67 +34,76 @@ struct Instruction {
 }
 
 impl Instruction {
-    fn parse(input: &str) -> Self {
-        let splitted: Vec<&str> = input.split(' ').collect();
-
-        Self {
-            count: splitted
-                .get(1)
-                .expect("count not found")
-                .parse::<usize>()
-                .expect("Couldnt parse count"),
-            from: splitted
-                .get(3)
-                .expect("count not found")
-                .parse::<usize>()
-                .expect("Couldnt parse count")
-                - 1,
-            to: splitted
-                .get(5)
-                .expect("count not found")
-                .parse::<usize>()
-                .expect("Couldnt parse count")
-                - 1,
-        }
+    fn parse(input: &str, line: &str, stack_count: usize) -> Result<Self, ParseError> {
+        let splitted: Vec<&str> = line.split(' ').collect();
+
+        let parse_stack = |field: Option<&&str>, name| {
+            let field = expect_field(input, line, field.copied(), name)?;
+
+            match parse_integer::<usize>(input, field)? {
+                stack if stack >= 1 && stack <= stack_count => Ok(stack - 1),
+                _ => Err(ParseError::new(
+                    input,
+                    field,
+                    ParseErrorKind::UnknownToken("stack"),
+                )),
+            }
+        };
+
+        Ok(Self {
+            count: parse_integer(
+                input,
+                expect_field(input, line, splitted.get(1).copied(), "count")?,
+            )?,
+            from: parse_stack(splitted.get(3), "source stack")?,
+            to: parse_stack(splitted.get(5), "target stack")?,
+        })
     }
 }
 
 struct DockBuilder;
 
 impl DockBuilder {
-    fn build(arrangement: &str) -> Dock {
-        let mut dock = Dock::new(DockBuilder::calculate_max_stacks(arrangement));
-
-        let mut lines: Vec<&str> = arrangement.split('\n').collect();
-        lines.reverse();
+    /// Crates are read from every fourth character, crates past the last stack are reported
+    fn build(input: &str, arrangement: &str) -> Result<Dock, ParseError> {
+        let mut dock = Dock::new(DockBuilder::calculate_max_stacks(input, arrangement)?);
 
-        lines.iter().for_each(|line| {
-            if !line.contains("[") {
-                return;
+        for line in arrangement.split('\n').rev() {
+            if !line.contains('[') {
+                continue;
             }
 
-            line.chars()
-                .collect::<Vec<char>>()
-                .chunks(4)
-                .enumerate()
-                .for_each(|(index, chunk)| {
-                    let item = &chunk[1];
-
-                    if *item != ' ' {
-                        dock.push(index, *item);
-                    }
-                });
-        });
+            for (offset, item) in line.char_indices().skip(1).step_by(4) {
+                if item == ' ' {
+                    continue;
+                }
+
+                dock.push(offset / 4, item).ok_or_else(|| {
+                    ParseError::new(
+                        input,
+                        &line[offset..offset + item.len_utf8()],
+                        ParseErrorKind::UnknownToken("stack"),
+                    )
+                })?;
+            }
+        }
 
-        dock
+        Ok(dock)
     }
 
-    fn calculate_max_stacks(arrangement: &str) -> usize {
-        arrangement
+    fn calculate_max_stacks(input: &str, arrangement: &str) -> Result<usize, ParseError> {
+        match arrangement
             .split('\n')
             .map(|line| (line.len() + 1) / 4)
             .max()
-            .expect("Empty line found")
+        {
+            Some(stack_count) if stack_count > 0 => Ok(stack_count),
+            _ => Err(ParseError::new(
+                input,
+                &arrangement[arrangement.len()..],
+                ParseErrorKind::MissingField("stacks"),
+            )),
+        }
     }
 }
 
@@ -111,59 +118,103 @@ impl CrateStack {
     }
 }
 
-fn calculate_challenge(input: &str, is_cranelift_9001: bool) -> String {
+/// Every instruction next to the line it came from
+type Procedure<'a> = Vec<(&'a str, Instruction)>;
+
+fn parse_input(input: &str) -> Result<(Dock, Procedure<'_>), ParseError> {
     let mut splitted = input.split("\n\n");
-    let arrangement = splitted.next().unwrap();
-    let procedure = splitted.next().unwrap();
+    let arrangement = expect_field(input, input, splitted.next(), "stacks")?;
+    let procedure = expect_field(input, input, splitted.next(), "rearrangement procedure")?;
 
-    let mut dock = DockBuilder::build(arrangement);
+    let dock = DockBuilder::build(input, arrangement)?;
 
-    let instructions: Vec<Instruction> = procedure
+    let instructions = procedure
         .split('\n')
-        .filter_map(|line| {
-            if line.is_empty() {
-                return None;
-            }
-            Some(Instruction::parse(line))
-        })
-        .collect();
+        .filter(|line| !line.is_empty())
+        .map(|line| Ok((line, Instruction::parse(input, line, dock.stack_count)?)))
+        .collect::<Result<_, ParseError>>()?;
 
-    instructions.iter().for_each(|instruction| {
-        if !is_cranelift_9001 {
-            for _ in 0..instruction.count {
-                let item = dock.pop(instruction.from);
-                dock.push(instruction.to, item);
-            }
-        } else {
-            let mut popped: Vec<char> = (0..instruction.count)
-                .map(|_| dock.pop(instruction.from))
-                .collect();
+    Ok((dock, instructions))
+}
 
+pub fn calculate_challenge(input: &str, is_cranelift_9001: bool) -> Result<String, ParseError> {
+    let (mut dock, instructions) = parse_input(input)?;
+
+    for (line, instruction) in instructions {
+        let mut popped: Vec<char> = (0..instruction.count)
+            .map(|_| dock.pop(instruction.from))
+            .collect::<Option<Vec<char>>>()
+            .ok_or_else(|| {
+                ParseError::new(
+                    input,
+                    line,
+                    ParseErrorKind::Invalid("not enough crates to move"),
+                )
+            })?;
+
+        if is_cranelift_9001 {
             popped.reverse();
+        }
 
-            popped
-                .iter()
-                .for_each(|item| dock.push(instruction.to, *item));
+        for item in popped {
+            dock.push(instruction.to, item).ok_or_else(|| {
+                ParseError::new(input, line, ParseErrorKind::UnknownToken("stack"))
+            })?;
         }
-    });
+    }
 
     let mut result = String::new();
     for stack in 0..dock.stack_count {
-        result.push(dock.pop(stack));
+        if let Some(item) = dock.pop(stack) {
+            result.push(item);
+        }
+    }
+
+    Ok(result)
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    fn year(&self) -> u16 {
+        2022
+    }
+
+    fn day(&self) -> u8 {
+        5
     }
 
-    result
+    fn title(&self) -> &'static str {
+        "Supply Stacks"
+    }
+
+    fn fixtures(&self) -> &'static [(&'static str, &'static str)] {
+        &[("example", include_str!("test_input.txt"))]
+    }
+
+    fn parse(&self, input: &str) -> Option<Result<(), ParseError>> {
+        Some(parse_input(input).map(drop))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, ParseError> {
+        Ok(calculate_challenge(input, false)?.into())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, ParseError> {
+        Ok(calculate_challenge(input, true)?.into())
+    }
 }
 
 #[cfg(test)]
 mod day5_tests {
     use crate::day5::calculate_challenge;
+    use crate::input::puzzle_input;
 
     #[test]
     fn test_input_challenge_1() {
         let test_input = include_str!("test_input.txt");
 
-        let result = calculate_challenge(test_input, false);
+        let result = calculate_challenge(test_input, false).unwrap();
         assert_eq!(result, "CMZ");
     }
 
@@ -171,23 +222,49 @@ mod day5_tests {
     fn test_input_challenge_2() {
         let test_input = include_str!("test_input.txt");
 
-        let result = calculate_challenge(test_input, true);
+        let result = calculate_challenge(test_input, true).unwrap();
         assert_eq!(result, "MCD");
     }
 
     #[test]
     fn get_challenge_1_output() {
-        let test_input = include_str!("input.txt");
+        let test_input = &puzzle_input(2022, 5);
 
-        let result = calculate_challenge(test_input, false);
+        let result = calculate_challenge(test_input, false).unwrap();
         println!("{}", result);
     }
 
     #[test]
     fn get_challenge_2_output() {
-        let test_input = include_str!("input.txt");
+        let test_input = &puzzle_input(2022, 5);
 
-        let result = calculate_challenge(test_input, true);
+        let result = calculate_challenge(test_input, true).unwrap();
         println!("{}", result);
     }
+
+    #[test]
+    fn reports_unknown_stack() {
+        let test_input = include_str!("test_input.txt").replace("from 2 to 1", "from 4 to 1");
+
+        let error = calculate_challenge(&test_input, false).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 6, column 13: unknown stack, found '4'"
+        );
+    }
+
+    #[test]
+    fn reports_malformed_arrangement() {
+        let error = calculate_challenge("[A] [B\n\nmove 1 from 1 to 1\n", false).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 6: unknown stack, found 'B'"
+        );
+
+        let error = calculate_challenge("\n\nmove 1 from 1 to 1\n", false).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 1: missing stacks, found ''"
+        );
+    }
 }
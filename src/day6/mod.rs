@@ -1,5 +1,26 @@
-fn parse_input(input: &str, subsection_size: usize) -> usize {
-    let chars: Vec<char> = input.chars().collect();
+use crate::error::{ParseError, ParseErrorKind};
+use crate::solution::{Answer, Solution};
+
+/// The datastream is a single line of lowercase letters
+fn parse_signal(input: &str) -> Result<Vec<char>, ParseError> {
+    let signal = input.trim_end();
+
+    match signal.find(|chr: char| !chr.is_ascii_lowercase()) {
+        None => Ok(signal.chars().collect()),
+        Some(index) => {
+            let chr_len = signal[index..].chars().next().unwrap().len_utf8();
+
+            Err(ParseError::new(
+                input,
+                &signal[index..index + chr_len],
+                ParseErrorKind::UnknownToken("character"),
+            ))
+        }
+    }
+}
+
+pub fn parse_input(input: &str, subsection_size: usize) -> Result<usize, ParseError> {
+    let chars = parse_signal(input)?;
     for (index, slice) in chars.windows(subsection_size).enumerate() {
         let mut found = false;
         for chr in 0..slice.len() {
@@ -11,43 +32,76 @@ fn parse_input(input: &str, subsection_size: usize) -> usize {
         }
 
         if !found {
-            return index + subsection_size;
+            return Ok(index + subsection_size);
         }
     }
 
-    panic!("Not found");
+    Err(ParseError::new(
+        input,
+        &input[input.len()..],
+        ParseErrorKind::Invalid("no marker found"),
+    ))
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    fn year(&self) -> u16 {
+        2022
+    }
+
+    fn day(&self) -> u8 {
+        6
+    }
+
+    fn title(&self) -> &'static str {
+        "Tuning Trouble"
+    }
+
+    fn parse(&self, input: &str) -> Option<Result<(), ParseError>> {
+        Some(parse_signal(input).map(drop))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, ParseError> {
+        Ok(parse_input(input, 4)?.into())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, ParseError> {
+        Ok(parse_input(input, 14)?.into())
+    }
 }
 
 #[cfg(test)]
 mod day_6_test {
     use crate::day6::parse_input;
+    use crate::input::puzzle_input;
 
     #[test]
     fn test_challenge1_inputs() {
-        assert_eq!(parse_input("bvwbjplbgvbhsrlpgdmjqwftvncz", 4), 5);
-        assert_eq!(parse_input("nppdvjthqldpwncqszvftbrmjlhg", 4), 6);
-        assert_eq!(parse_input("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 4), 10);
-        assert_eq!(parse_input("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 4), 11);
+        assert_eq!(parse_input("bvwbjplbgvbhsrlpgdmjqwftvncz", 4), Ok(5));
+        assert_eq!(parse_input("nppdvjthqldpwncqszvftbrmjlhg", 4), Ok(6));
+        assert_eq!(parse_input("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 4), Ok(10));
+        assert_eq!(parse_input("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 4), Ok(11));
     }
 
     #[test]
     fn test_challenge2_inputs() {
-        assert_eq!(parse_input("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 14), 19);
-        assert_eq!(parse_input("bvwbjplbgvbhsrlpgdmjqwftvncz", 14), 23);
-        assert_eq!(parse_input("nppdvjthqldpwncqszvftbrmjlhg", 14), 23);
-        assert_eq!(parse_input("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 14), 29);
-        assert_eq!(parse_input("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 14), 26);
+        assert_eq!(parse_input("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 14), Ok(19));
+        assert_eq!(parse_input("bvwbjplbgvbhsrlpgdmjqwftvncz", 14), Ok(23));
+        assert_eq!(parse_input("nppdvjthqldpwncqszvftbrmjlhg", 14), Ok(23));
+        assert_eq!(parse_input("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 14), Ok(29));
+        assert_eq!(parse_input("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 14), Ok(26));
     }
 
     #[test]
     fn get_challenge1_output() {
-        let input = include_str!("input.txt");
-        println!("{}", parse_input(input, 4));
+        let input = &puzzle_input(2022, 6);
+        println!("{}", parse_input(input, 4).unwrap());
     }
 
     #[test]
     fn get_challenge2_output() {
-        let input = include_str!("input.txt");
-        println!("{}", parse_input(input, 14));
+        let input = &puzzle_input(2022, 6);
+        println!("{}", parse_input(input, 14).unwrap());
     }
 }
@@ -1,7 +1,6 @@
+use crate::error::{expect_field, parse_integer, ParseError, ParseErrorKind};
+use crate::solution::{Answer, Solution};
 use std::collections::HashMap;
-use std::fs;
-use std::ops::Deref;
-use std::path::Path;
 
 #[derive(Debug)]
 struct File(usize);
@@ -49,7 +48,7 @@ impl Directory {
 }
 
 #[derive(Debug)]
-struct FileSystem {
+pub struct FileSystem {
     current_path: Vec<String>,
     root: Directory,
 }
@@ -74,20 +73,17 @@ impl FileSystem {
         };
     }
 
-    fn get_current_dir(&mut self) -> &mut Directory {
+    fn get_current_dir(&mut self) -> Option<&mut Directory> {
         let mut dir = &mut self.root;
         for path in &self.current_path {
-            dir = dir
-                .sub_directories
-                .get_mut(path)
-                .expect("Directory not found");
+            dir = dir.sub_directories.get_mut(path)?;
         }
 
-        dir
+        Some(dir)
     }
 
-    fn get_files(&mut self) -> &HashMap<String, File> {
-        &self.get_current_dir().files
+    fn get_files(&mut self) -> Option<&HashMap<String, File>> {
+        Some(&self.get_current_dir()?.files)
     }
 }
 
@@ -98,12 +94,12 @@ struct Command<'a> {
 }
 
 impl<'a> Command<'a> {
-    fn parse(input: &'a str) -> Self {
-        let mut splitted = input.split('\n').into_iter();
-        Self {
-            data: CommandType::parse(splitted.next().expect("Command wasn't parseable")),
+    fn parse(input: &str, data: &'a str) -> Result<Self, ParseError> {
+        let mut splitted = data.split('\n').into_iter();
+        Ok(Self {
+            data: CommandType::parse(input, splitted.next().unwrap())?,
             result: splitted.collect::<Vec<&'a str>>(),
-        }
+        })
     }
 }
 
@@ -114,67 +110,77 @@ enum CommandType<'a> {
 }
 
 impl<'a> CommandType<'a> {
-    fn parse(input: &'a str) -> Self {
-        let mut splitted = input.split(' ');
-
-        match splitted.next().expect("Command not parseable") {
-            "cd" => CommandType::Cd(splitted.next().expect("Couldn't parse cd args")),
-            "ls" => CommandType::Ls,
-            _ => unimplemented!("Command not implemented"),
+    fn parse(input: &str, line: &'a str) -> Result<Self, ParseError> {
+        let mut splitted = line.split(' ');
+
+        match splitted.next().unwrap() {
+            "cd" => Ok(CommandType::Cd(expect_field(
+                input,
+                line,
+                splitted.next(),
+                "directory",
+            )?)),
+            "ls" => Ok(CommandType::Ls),
+            command => Err(ParseError::new(
+                input,
+                command,
+                ParseErrorKind::UnknownToken("command"),
+            )),
         }
     }
 }
 
-fn parse_commands(input: &str) -> Vec<Command> {
+fn parse_commands(input: &str) -> Result<Vec<Command<'_>>, ParseError> {
     input
         .split("$ ")
-        .filter_map(|data| {
-            if data.is_empty() {
-                return None;
-            }
-
-            let command = Command::parse(data);
-
-            Some(command)
-        })
+        .filter(|data| !data.is_empty())
+        .map(|data| Command::parse(input, data))
         .collect()
 }
 
-fn build_fs(input: &str) -> FileSystem {
+pub fn build_fs(input: &str) -> Result<FileSystem, ParseError> {
     const DIR: &'static str = "dir ";
 
     let mut fs = FileSystem::new();
 
-    let commands: Vec<Command> = parse_commands(input);
+    let commands: Vec<Command> = parse_commands(input)?;
 
-    commands.iter().for_each(|command| match command.data {
-        CommandType::Cd(dir) => {
-            fs.navigate(dir);
-        }
-        CommandType::Ls => command.result.iter().for_each(|output| {
-            let mut current_directory = fs.get_current_dir();
-
-            if output.starts_with(DIR) {
-                current_directory.upsert_directory(&output[DIR.len()..output.len()]);
-            } else if !output.is_empty() {
-                // It's a file
-                let mut splitted = output.split(' ');
-                let file_size = splitted
-                    .next()
-                    .expect("No size found")
-                    .parse::<usize>()
-                    .expect("Not a number");
-                let file_name = splitted.next().expect("No name found");
-
-                current_directory.upsert_file(file_name, file_size);
+    for command in &commands {
+        match command.data {
+            CommandType::Cd(dir) => {
+                fs.navigate(dir);
+
+                if fs.get_current_dir().is_none() {
+                    return Err(ParseError::new(
+                        input,
+                        dir,
+                        ParseErrorKind::UnknownToken("directory"),
+                    ));
+                }
             }
-        }),
-    });
+            CommandType::Ls => {
+                for output in &command.result {
+                    let current_directory = fs.get_current_dir().unwrap();
+
+                    if output.starts_with(DIR) {
+                        current_directory.upsert_directory(&output[DIR.len()..output.len()]);
+                    } else if !output.is_empty() {
+                        // It's a file
+                        let mut splitted = output.split(' ');
+                        let file_size = parse_integer(input, splitted.next().unwrap())?;
+                        let file_name = expect_field(input, output, splitted.next(), "file name")?;
+
+                        current_directory.upsert_file(file_name, file_size);
+                    }
+                }
+            }
+        }
+    }
 
-    fs
+    Ok(fs)
 }
 
-fn challenge_1(fs: &FileSystem) -> usize {
+pub fn challenge_1(fs: &FileSystem) -> usize {
     challenge_1_calculator(&fs.root)
 }
 
@@ -194,10 +200,10 @@ fn challenge_1_calculator(directory: &Directory) -> usize {
     my_size + other_sizes
 }
 
-fn challenge_2(fs: FileSystem) -> usize {
-    let required_disk_space = 30000000;
-    let unused_disk_space = TOTAL_DISK_SPACE - fs.root.get_size();
-    let should_free = required_disk_space - unused_disk_space;
+pub fn challenge_2(fs: FileSystem) -> usize {
+    let required_disk_space: usize = 30000000;
+    let unused_disk_space = TOTAL_DISK_SPACE.saturating_sub(fs.root.get_size());
+    let should_free = required_disk_space.saturating_sub(unused_disk_space);
 
     challenge_2_calculator(&fs.root, should_free, 1000000000000)
 }
@@ -225,24 +231,55 @@ fn challenge_2_calculator(
 
 const TOTAL_DISK_SPACE: usize = 70000000;
 
+pub struct Day7;
+
+impl Solution for Day7 {
+    fn year(&self) -> u16 {
+        2022
+    }
+
+    fn day(&self) -> u8 {
+        7
+    }
+
+    fn title(&self) -> &'static str {
+        "No Space Left On Device"
+    }
+
+    fn fixtures(&self) -> &'static [(&'static str, &'static str)] {
+        &[("example", include_str!("test_input.txt"))]
+    }
+
+    fn parse(&self, input: &str) -> Option<Result<(), ParseError>> {
+        Some(build_fs(input).map(drop))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, ParseError> {
+        Ok(challenge_1(&build_fs(input)?).into())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, ParseError> {
+        Ok(challenge_2(build_fs(input)?).into())
+    }
+}
+
 #[cfg(test)]
 mod day_7_tests {
-    use crate::day7::{
-        build_fs, challenge_1, challenge_2, challenge_2_calculator, TOTAL_DISK_SPACE,
-    };
+    use crate::day7::{build_fs, challenge_1, challenge_2};
+    use crate::input::puzzle_input;
 
     #[test]
     fn test_input_challenge_1() {
         let input = include_str!("test_input.txt");
-        let file_system = build_fs(input);
+        let file_system = build_fs(input).unwrap();
         let result = challenge_1(&file_system);
         assert_eq!(result, 95437);
     }
 
     #[test]
     fn calculate_challenge_1() {
-        let input = include_str!("input.txt");
-        let file_system = build_fs(input);
+        let input = &puzzle_input(2022, 7);
+        let file_system = build_fs(input).unwrap();
         let result = challenge_1(&file_system);
         println!("{}", result);
     }
@@ -250,16 +287,33 @@ mod day_7_tests {
     #[test]
     fn test_input_challenge_2() {
         let input = include_str!("test_input.txt");
-        let file_system = build_fs(input);
+        let file_system = build_fs(input).unwrap();
 
         assert_eq!(challenge_2(file_system), 24933642);
     }
 
     #[test]
     fn calculate_challenge_2() {
-        let input = include_str!("input.txt");
-        let file_system = build_fs(input);
+        let input = &puzzle_input(2022, 7);
+        let file_system = build_fs(input).unwrap();
 
         println!("{}", challenge_2(file_system));
     }
+
+    #[test]
+    fn small_disk_usage_needs_no_freeing() {
+        let file_system = build_fs("$ cd /\n$ ls\n100 a\n").unwrap();
+
+        assert_eq!(challenge_2(file_system), 100);
+    }
+
+    #[test]
+    fn reports_unknown_command() {
+        let error = build_fs("$ cd /\n$ ls\n14848514 b.txt\n$ rm b.txt\n").unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "line 4, column 3: unknown command, found 'rm'"
+        );
+    }
 }
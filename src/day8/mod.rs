@@ -1,51 +1,54 @@
-fn challenge_1(input: &str) -> usize {
-    let grid = build_grid(input);
+use crate::error::{ParseError, ParseErrorKind};
+use crate::grid::Grid;
+use crate::solution::{Answer, Solution};
 
-    let mut edge_visible = (grid.len() - 1) * 2;
-    edge_visible = edge_visible + ((grid.get(0).unwrap().len() - 1) * 2);
+pub fn challenge_1(input: &str) -> Result<usize, ParseError> {
+    let grid = build_grid(input)?;
+
+    let mut edge_visible = (grid.height() - 1) * 2;
+    edge_visible = edge_visible + ((grid.width() - 1) * 2);
 
     let mut result = 0;
 
-    for y in 1..grid.len() - 1 {
-        let row = grid.get(y).unwrap();
-        for x in 1..row.len() - 1 {
+    for y in 1..grid.height() - 1 {
+        for x in 1..grid.width() - 1 {
             result += calculate_point(x, y, &grid, false);
         }
     }
-    edge_visible + result
+    Ok(edge_visible + result)
 }
 
-fn challenge_2(input: &str) -> usize {
-    let grid = build_grid(input);
+pub fn challenge_2(input: &str) -> Result<usize, ParseError> {
+    let grid = build_grid(input)?;
 
     let mut highest = 0;
-    for y in 1..grid.len() - 1 {
-        let row = grid.get(y).unwrap();
-        for x in 1..row.len() - 1 {
+    for y in 1..grid.height() - 1 {
+        for x in 1..grid.width() - 1 {
             let result = calculate_point(x, y, &grid, true);
             if result > highest {
                 highest = result;
             }
         }
     }
-    highest
+    Ok(highest)
 }
 
-fn build_grid(input: &str) -> Vec<Vec<usize>> {
-    let grid: Vec<Vec<usize>> = input
-        .split('\n')
-        .filter_map(|line| {
-            if line.is_empty() {
-                return None;
-            }
-            Some(
-                line.chars()
-                    .map(|char| char.to_string().parse::<usize>().unwrap())
-                    .collect(),
-            )
-        })
-        .collect();
-    grid
+fn build_grid(input: &str) -> Result<Grid<usize>, ParseError> {
+    let grid = Grid::parse(input, |char| {
+        char.to_digit(10)
+            .map(|digit| digit as usize)
+            .ok_or(ParseErrorKind::ExpectedInteger)
+    })?;
+
+    if grid.is_empty() {
+        return Err(ParseError::new(
+            input,
+            input,
+            ParseErrorKind::Invalid("no trees found"),
+        ));
+    }
+
+    Ok(grid)
 }
 
 fn is_visible_in_line(line: &[usize], index: usize) -> bool {
@@ -95,12 +98,9 @@ fn calculate_viewing_score(line: &[usize], index: usize) -> usize {
     total_left * total_right
 }
 
-fn calculate_point(x: usize, y: usize, grid: &Vec<Vec<usize>>, challenge_2: bool) -> usize {
-    let horizontal_line = grid.get(y).unwrap();
-    let vertical_line: Vec<usize> = grid
-        .iter()
-        .map(|horizontal_line| *horizontal_line.get(x).expect(&format!("Couldnt get {}", x)))
-        .collect();
+fn calculate_point(x: usize, y: usize, grid: &Grid<usize>, challenge_2: bool) -> usize {
+    let horizontal_line = grid.row(y);
+    let vertical_line: Vec<usize> = grid.column(x).copied().collect();
 
     if challenge_2 {
         return calculate_viewing_score(horizontal_line, x)
@@ -112,14 +112,47 @@ fn calculate_point(x: usize, y: usize, grid: &Vec<Vec<usize>>, challenge_2: bool
     return 0;
 }
 
+pub struct Day8;
+
+impl Solution for Day8 {
+    fn year(&self) -> u16 {
+        2022
+    }
+
+    fn day(&self) -> u8 {
+        8
+    }
+
+    fn title(&self) -> &'static str {
+        "Treetop Tree House"
+    }
+
+    fn fixtures(&self) -> &'static [(&'static str, &'static str)] {
+        &[("example", include_str!("test_input.txt"))]
+    }
+
+    fn parse(&self, input: &str) -> Option<Result<(), ParseError>> {
+        Some(build_grid(input).map(drop))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, ParseError> {
+        Ok(challenge_1(input)?.into())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, ParseError> {
+        Ok(challenge_2(input)?.into())
+    }
+}
+
 #[cfg(test)]
 mod day_8_tests {
     use crate::day8::{challenge_1, challenge_2, is_visible_in_line};
+    use crate::input::puzzle_input;
 
     #[test]
     fn test_challenge1_output() {
         let input = include_str!("test_input.txt");
-        let result = challenge_1(input);
+        let result = challenge_1(input).unwrap();
 
         assert_eq!(result, 21);
     }
@@ -127,7 +160,7 @@ mod day_8_tests {
     #[test]
     fn test_challenge2_output() {
         let input = include_str!("test_input.txt");
-        let result = challenge_2(input);
+        let result = challenge_2(input).unwrap();
 
         assert_eq!(result, 8);
     }
@@ -142,16 +175,16 @@ mod day_8_tests {
 
     #[test]
     fn calculate_challenge1_output() {
-        let input = include_str!("input.txt");
-        let result = challenge_1(input);
+        let input = &puzzle_input(2022, 8);
+        let result = challenge_1(input).unwrap();
 
         println!("{}", result)
     }
 
     #[test]
     fn calculate_challenge2_output() {
-        let input = include_str!("input.txt");
-        let result = challenge_2(input);
+        let input = &puzzle_input(2022, 8);
+        let result = challenge_2(input).unwrap();
 
         println!("{}", result)
     }
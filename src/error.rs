@@ -0,0 +1,133 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ParseErrorKind {
+    ExpectedInteger,
+    MissingField(&'static str),
+    UnknownToken(&'static str),
+    Invalid(&'static str),
+}
+
+impl Display for ParseErrorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseErrorKind::ExpectedInteger => f.write_str("expected integer"),
+            ParseErrorKind::MissingField(field) => write!(f, "missing {field}"),
+            ParseErrorKind::UnknownToken(token) => write!(f, "unknown {token}"),
+            ParseErrorKind::Invalid(reason) => f.write_str(reason),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ParseError {
+    pub puzzle: Option<(u16, u8)>,
+    pub line: usize,
+    pub column: usize,
+    pub found: String,
+    pub kind: ParseErrorKind,
+}
+
+impl ParseError {
+    /// `found` has to be a slice of `input`, its offset is used to find the line and column
+    pub fn new(input: &str, found: &str, kind: ParseErrorKind) -> Self {
+        let offset = (found.as_ptr() as usize).wrapping_sub(input.as_ptr() as usize);
+        debug_assert!(offset <= input.len(), "'{found}' is not part of the input");
+        let offset = offset.min(input.len());
+
+        let line_start = input[..offset].rfind('\n').map_or(0, |index| index + 1);
+
+        Self {
+            puzzle: None,
+            line: input[..offset].matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            found: found.to_owned(),
+            kind,
+        }
+    }
+
+    pub fn in_puzzle(self, year: u16, day: u8) -> Self {
+        Self {
+            puzzle: Some((year, day)),
+            ..self
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if let Some((year, day)) = self.puzzle {
+            write!(f, "{year} day {day}, ")?;
+        }
+
+        write!(
+            f,
+            "line {}, column {}: {}, found '{}'",
+            self.line, self.column, self.kind, self.found
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+pub fn parse_integer<T: FromStr>(input: &str, text: &str) -> Result<T, ParseError> {
+    text.parse::<T>()
+        .map_err(|_| ParseError::new(input, text, ParseErrorKind::ExpectedInteger))
+}
+
+/// Missing fields are reported at the end of the line they should have been on
+pub fn expect_field<'a>(
+    input: &str,
+    line: &'a str,
+    field: Option<&'a str>,
+    name: &'static str,
+) -> Result<&'a str, ParseError> {
+    field.ok_or_else(|| {
+        ParseError::new(
+            input,
+            &line[line.len()..],
+            ParseErrorKind::MissingField(name),
+        )
+    })
+}
+
+#[cfg(test)]
+mod error_tests {
+    use crate::error::{expect_field, parse_integer, ParseError, ParseErrorKind};
+
+    #[test]
+    fn finds_line_and_column() {
+        let input = "1 2 3\n4 x 6\n";
+        let found = &input[8..9];
+
+        let error = ParseError::new(input, found, ParseErrorKind::ExpectedInteger);
+
+        assert_eq!((error.line, error.column), (2, 3));
+        assert_eq!(error.found, "x");
+    }
+
+    #[test]
+    fn formats_with_puzzle() {
+        let input = "7 6 4\n1  2\n";
+        let error = parse_integer::<u32>(input, &input[8..8])
+            .unwrap_err()
+            .in_puzzle(2024, 2);
+
+        assert_eq!(
+            error.to_string(),
+            "2024 day 2, line 2, column 3: expected integer, found ''"
+        );
+    }
+
+    #[test]
+    fn missing_field_points_at_end_of_line() {
+        let input = "move 1 from\nmove 2 from 1 to 3";
+        let line = &input[..11];
+
+        let error = expect_field(input, line, None, "source stack").unwrap_err();
+
+        assert_eq!((error.line, error.column), (1, 12));
+        assert_eq!(error.kind, ParseErrorKind::MissingField("source stack"));
+    }
+}
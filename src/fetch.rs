@@ -0,0 +1,155 @@
+/// Session cookie used to download inputs, copy it from the browser after logging in
+pub const SESSION_VARIABLE: &str = "AOC_SESSION";
+/// Overrides where inputs are downloaded from, mostly useful for testing
+pub const BASE_URL_VARIABLE: &str = "AOC_BASE_URL";
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+const USER_AGENT: &str = concat!(
+    "adventofcode/",
+    env!("CARGO_PKG_VERSION"),
+    " (input cache, fetches each day once)"
+);
+
+/// Downloads puzzle inputs, see `InputProvider::with_fetcher` for the cache in front of it
+#[derive(Debug, Clone)]
+pub struct InputFetcher {
+    base_url: String,
+    session: String,
+}
+
+impl InputFetcher {
+    pub fn new(base_url: impl Into<String>, session: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_owned(),
+            session: session.into(),
+        }
+    }
+
+    /// Only available when a session cookie is set
+    pub fn from_env() -> Option<Self> {
+        let session = std::env::var(SESSION_VARIABLE).ok()?;
+        let base_url =
+            std::env::var(BASE_URL_VARIABLE).unwrap_or_else(|_| DEFAULT_BASE_URL.to_owned());
+
+        Some(Self::new(base_url, session))
+    }
+
+    pub fn url(&self, year: u16, day: u8) -> String {
+        format!("{}/{year}/day/{day}/input", self.base_url)
+    }
+
+    pub fn fetch(&self, year: u16, day: u8) -> Result<String, String> {
+        let url = self.url(year, day);
+
+        let response = ureq::get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .set("User-Agent", USER_AGENT)
+            .call()
+            .map_err(|err| match err {
+                ureq::Error::Status(status, _) => {
+                    format!("Couldn't download {url}: server answered {status}")
+                }
+                ureq::Error::Transport(err) => format!("Couldn't download input: {err}"),
+            })?;
+
+        response
+            .into_string()
+            .map_err(|err| format!("Couldn't download {url}: {err}"))
+    }
+}
+
+#[cfg(test)]
+mod fetch_tests {
+    use crate::fetch::InputFetcher;
+    use crate::input::InputProvider;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::path::PathBuf;
+    use std::sync::{Arc, Mutex};
+
+    /// Answers every request with `status` and `body`, returns the base url and the requests it saw
+    fn mock_server(status: &'static str, body: &'static str) -> (String, Arc<Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        let seen = requests.clone();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut request = String::new();
+
+                for line in BufReader::new(&stream).lines() {
+                    let line = line.unwrap();
+                    if line.is_empty() {
+                        break;
+                    }
+                    request.push_str(&line);
+                    request.push('\n');
+                }
+
+                seen.lock().unwrap().push(request);
+
+                write!(
+                    stream,
+                    "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+            }
+        });
+
+        (base_url, requests)
+    }
+
+    fn cache_dir(name: &str) -> PathBuf {
+        let directory =
+            std::env::temp_dir().join(format!("aoc-cache-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&directory);
+
+        directory
+    }
+
+    #[test]
+    fn downloads_with_session_cookie() {
+        let (base_url, requests) = mock_server("200 OK", "1 2 3\n");
+        let fetcher = InputFetcher::new(format!("{base_url}/"), "abc123");
+
+        assert_eq!(fetcher.fetch(2024, 2).unwrap(), "1 2 3\n");
+
+        let requests = requests.lock().unwrap();
+        assert!(requests[0].starts_with("GET /2024/day/2/input "));
+        assert!(requests[0].contains("session=abc123"));
+    }
+
+    #[test]
+    fn caches_downloaded_inputs() {
+        let (base_url, requests) = mock_server("200 OK", "3   4\n4   3\n");
+        let directory = cache_dir("hit");
+        let provider =
+            InputProvider::new(&directory).with_fetcher(InputFetcher::new(base_url, "abc123"));
+
+        assert_eq!(provider.load(2024, 1).unwrap(), "3   4\n4   3\n");
+        assert_eq!(provider.load(2024, 1).unwrap(), "3   4\n4   3\n");
+
+        assert_eq!(requests.lock().unwrap().len(), 1);
+        assert!(provider.path(2024, 1).exists());
+
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn failed_downloads_are_not_cached() {
+        let (base_url, _) = mock_server("404 Not Found", "Please don't repeatedly request this");
+        let directory = cache_dir("miss");
+        let provider =
+            InputProvider::new(&directory).with_fetcher(InputFetcher::new(base_url, "abc123"));
+
+        let error = provider.load(2024, 25).unwrap_err();
+
+        assert!(error.ends_with("server answered 404"), "{error}");
+        assert!(!provider.path(2024, 25).exists());
+
+        let _ = std::fs::remove_dir_all(directory);
+    }
+}
@@ -0,0 +1,382 @@
+use crate::error::{ParseError, ParseErrorKind};
+use crate::geometry::{Direction8, Point};
+use std::fmt::{Display, Formatter};
+use std::ops::{Index, IndexMut};
+
+/// Rectangular grid stored row by row, `(0, 0)` is the top left corner
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Parses one cell per character, empty lines are skipped
+    ///
+    /// Errors from `parse_cell` are reported at the character that caused them.
+    pub fn parse(
+        input: &str,
+        mut parse_cell: impl FnMut(char) -> Result<T, ParseErrorKind>,
+    ) -> Result<Self, ParseError> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::with_capacity(input.len());
+
+        for line in input.lines().filter(|line| !line.is_empty()) {
+            let row_start = cells.len();
+
+            for (index, char) in line.char_indices() {
+                let cell = parse_cell(char).map_err(|kind| {
+                    ParseError::new(input, &line[index..index + char.len_utf8()], kind)
+                })?;
+                cells.push(cell);
+            }
+
+            if *width.get_or_insert(cells.len() - row_start) != cells.len() - row_start {
+                return Err(ParseError::new(
+                    input,
+                    line,
+                    ParseErrorKind::Invalid("rows differ in length"),
+                ));
+            }
+
+            height += 1;
+        }
+
+        Ok(Self {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        point.x >= 0
+            && point.y >= 0
+            && (point.x as usize) < self.width
+            && (point.y as usize) < self.height
+    }
+
+    fn index_of(&self, point: Point) -> Option<usize> {
+        self.contains(point)
+            .then(|| point.y as usize * self.width + point.x as usize)
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.index_of(point).map(|index| &self.cells[index])
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.index_of(point).map(|index| &mut self.cells[index])
+    }
+
+    /// Every cell with its position, row by row
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells.iter().enumerate().map(|(index, cell)| {
+            let position = Point::new((index % self.width) as isize, (index / self.width) as isize);
+            (position, cell)
+        })
+    }
+
+    pub fn position(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Point> {
+        self.iter()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(position, _)| position)
+    }
+
+    fn cells_at(&self, points: impl Iterator<Item = Point>) -> impl Iterator<Item = (Point, &T)> {
+        points.filter_map(|point| Some((point, self.get(point)?)))
+    }
+
+    /// Orthogonal neighbours inside the grid, clockwise starting above
+    pub fn neighbours_4(&self, point: Point) -> impl Iterator<Item = (Point, &T)> {
+        self.cells_at(point.neighbours_4())
+    }
+
+    /// Orthogonal and diagonal neighbours inside the grid, clockwise starting above
+    pub fn neighbours_8(&self, point: Point) -> impl Iterator<Item = (Point, &T)> {
+        self.cells_at(point.neighbours_8())
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(
+            x < self.width,
+            "x {x} is outside a grid {} wide",
+            self.width
+        );
+        self.cells[x..].iter().step_by(self.width)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// The cells from `start` onwards in `direction`, until the line leaves the grid
+    pub fn line(&self, start: Point, direction: Direction8) -> impl Iterator<Item = &T> {
+        let step = Point::from(direction);
+
+        (0..)
+            .map(move |distance| self.get(start + step * distance))
+            .take_while(Option::is_some)
+            .flatten()
+    }
+
+    /// Diagonals going down and to the right, starting at the bottom left corner
+    pub fn diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let (width, height) = (self.width as isize, self.height as isize);
+        let starts = (0..height).rev().map(|y| Point::new(0, y));
+        let starts = starts.chain((1..width).map(|x| Point::new(x, 0)));
+
+        starts.map(|start| self.line(start, Direction8::DownRight))
+    }
+
+    /// Diagonals going down and to the left, starting at the top left corner
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let (width, height) = (self.width as isize, self.height as isize);
+        let starts = (0..width).map(|x| Point::new(x, 0));
+        let starts = starts.chain((1..height).map(move |y| Point::new(width - 1, y)));
+
+        starts.map(|start| self.line(start, Direction8::DownLeft))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Builds a grid from a cell lookup in the coordinates of the new grid
+    fn from_fn(width: usize, height: usize, mut cell: impl FnMut(usize, usize) -> T) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| cell(x, y))
+            .collect();
+
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(self.height, self.width, |x, y| self[(y, x)].clone())
+    }
+
+    pub fn rotate_clockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(self.height, self.width, |x, y| {
+            self[(y, self.height - 1 - x)].clone()
+        })
+    }
+
+    pub fn rotate_counterclockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(self.height, self.width, |x, y| {
+            self[(self.width - 1 - y, x)].clone()
+        })
+    }
+
+    /// Mirrors left to right
+    pub fn flip_horizontal(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(self.width, self.height, |x, y| {
+            self[(self.width - 1 - x, y)].clone()
+        })
+    }
+
+    /// Mirrors top to bottom
+    pub fn flip_vertical(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(self.width, self.height, |x, y| {
+            self[(x, self.height - 1 - y)].clone()
+        })
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        assert!(
+            x < self.width,
+            "x {x} is outside a grid {} wide",
+            self.width
+        );
+        &self.cells[y * self.width + x]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        assert!(
+            x < self.width,
+            "x {x} is outside a grid {} wide",
+            self.width
+        );
+        &mut self.cells[y * self.width + x]
+    }
+}
+
+/// Prints the grid the way puzzle inputs look, one line per row
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod grid_tests {
+    use crate::error::ParseErrorKind;
+    use crate::geometry::{Direction8, Point};
+    use crate::grid::Grid;
+
+    fn chars(input: &str) -> Grid<char> {
+        Grid::parse(input, Ok).unwrap()
+    }
+
+    fn text<'a>(cells: impl Iterator<Item = &'a char>) -> String {
+        cells.collect()
+    }
+
+    #[test]
+    fn parses_and_displays() {
+        let grid = chars("abc\ndef\n");
+
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get(Point::new(2, 1)), Some(&'f'));
+        assert_eq!(grid.get(Point::new(-1, 0)), None);
+        assert_eq!(grid.get(Point::new(3, 0)), None);
+        assert_eq!(grid[(1, 0)], 'b');
+        assert_eq!(grid.to_string(), "abc\ndef\n");
+    }
+
+    #[test]
+    fn reports_cell_and_row_errors() {
+        let digits = |char: char| char.to_digit(10).ok_or(ParseErrorKind::ExpectedInteger);
+
+        let error = Grid::parse("123\n4x6\n", digits).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 2: expected integer, found 'x'"
+        );
+
+        let error = Grid::parse("123\n45\n", digits).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 1: rows differ in length, found '45'"
+        );
+    }
+
+    #[test]
+    fn finds_neighbours() {
+        let grid = chars("abc\ndef\nghi\n");
+
+        let corner: String = grid.neighbours_8(Point::ORIGIN).map(|(_, c)| c).collect();
+        assert_eq!(corner, "bed");
+
+        let centre: Vec<_> = grid.neighbours_4(Point::new(1, 1)).collect();
+        assert_eq!(
+            centre,
+            [
+                (Point::new(1, 0), &'b'),
+                (Point::new(2, 1), &'f'),
+                (Point::new(1, 2), &'h'),
+                (Point::new(0, 1), &'d')
+            ]
+        );
+    }
+
+    #[test]
+    fn views_rows_columns_and_diagonals() {
+        let grid = chars("abc\ndef\n");
+
+        assert_eq!(grid.row(1), ['d', 'e', 'f']);
+        assert_eq!(text(grid.column(2)), "cf");
+        assert_eq!(
+            grid.columns().map(text).collect::<Vec<_>>(),
+            ["ad", "be", "cf"]
+        );
+        assert_eq!(
+            grid.diagonals().map(text).collect::<Vec<_>>(),
+            ["d", "ae", "bf", "c"]
+        );
+        assert_eq!(
+            grid.anti_diagonals().map(text).collect::<Vec<_>>(),
+            ["a", "bd", "ce", "f"]
+        );
+        assert_eq!(text(grid.line(Point::new(2, 1), Direction8::Left)), "fed");
+    }
+
+    #[test]
+    #[should_panic(expected = "x 3 is outside a grid 3 wide")]
+    fn column_outside_the_grid() {
+        let grid = chars("abc\ndef\n");
+
+        let _ = grid.column(3);
+    }
+
+    #[test]
+    fn transforms() {
+        let grid = chars("abc\ndef\n");
+
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf\n");
+        assert_eq!(grid.rotate_clockwise().to_string(), "da\neb\nfc\n");
+        assert_eq!(grid.rotate_counterclockwise().to_string(), "cf\nbe\nad\n");
+        assert_eq!(grid.flip_horizontal().to_string(), "cba\nfed\n");
+        assert_eq!(grid.flip_vertical().to_string(), "def\nabc\n");
+        assert_eq!(grid.rotate_clockwise().rotate_counterclockwise(), grid);
+    }
+}
@@ -0,0 +1,195 @@
+use crate::fetch::InputFetcher;
+use crate::solution::Solution;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// Overrides the directory puzzle inputs are read from
+pub const INPUT_DIR_VARIABLE: &str = "AOC_INPUTS";
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum InputSource {
+    /// The puzzle's own input, looked up in the input directory
+    Puzzle,
+    Stdin,
+    File(PathBuf),
+    /// One of the examples from the puzzle text, see `Solution::fixtures`
+    Fixture(String),
+}
+
+impl InputSource {
+    /// `-` reads stdin, anything else is a path
+    pub fn parse(value: &str) -> Self {
+        match value {
+            "-" => InputSource::Stdin,
+            _ => InputSource::File(PathBuf::from(value)),
+        }
+    }
+}
+
+/// Resolves puzzle inputs from `{directory}/{year}/day{NN}.txt`, which doubles as the download cache
+#[derive(Debug, Clone)]
+pub struct InputProvider {
+    directory: PathBuf,
+    fetcher: Option<InputFetcher>,
+}
+
+impl Default for InputProvider {
+    fn default() -> Self {
+        let provider = Self::offline();
+
+        match InputFetcher::from_env() {
+            Some(fetcher) => provider.with_fetcher(fetcher),
+            None => provider,
+        }
+    }
+}
+
+impl InputProvider {
+    /// Only reads the input directory, even when a session for downloading is configured
+    pub fn offline() -> Self {
+        match std::env::var_os(INPUT_DIR_VARIABLE) {
+            Some(directory) => Self::new(directory),
+            None => Self::new(Path::new(env!("CARGO_MANIFEST_DIR")).join("inputs")),
+        }
+    }
+
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self {
+            directory: directory.into(),
+            fetcher: None,
+        }
+    }
+
+    /// Downloads inputs that aren't cached yet, a cached day is never fetched again
+    pub fn with_fetcher(self, fetcher: InputFetcher) -> Self {
+        Self {
+            fetcher: Some(fetcher),
+            ..self
+        }
+    }
+
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    pub fn path(&self, year: u16, day: u8) -> PathBuf {
+        self.directory
+            .join(year.to_string())
+            .join(format!("day{day:02}.txt"))
+    }
+
+    pub fn load(&self, year: u16, day: u8) -> Result<String, String> {
+        let path = self.path(year, day);
+
+        match &self.fetcher {
+            Some(fetcher) if !path.exists() => {
+                let input = fetcher.fetch(year, day)?;
+                write_file(&path, &input)?;
+
+                Ok(input)
+            }
+            _ => read_file(&path),
+        }
+    }
+
+    pub fn resolve(&self, source: &InputSource, solution: &dyn Solution) -> Result<String, String> {
+        match source {
+            InputSource::Puzzle => self.load(solution.year(), solution.day()),
+            InputSource::Stdin => {
+                let mut input = String::new();
+                std::io::stdin()
+                    .read_to_string(&mut input)
+                    .map_err(|err| format!("Couldn't read stdin: {err}"))?;
+
+                Ok(input)
+            }
+            InputSource::File(path) => read_file(path),
+            InputSource::Fixture(name) => solution
+                .fixtures()
+                .iter()
+                .find(|(fixture, _)| fixture == name)
+                .map(|(_, input)| input.to_string())
+                .ok_or_else(|| {
+                    format!(
+                        "{} day {} has no fixture named {name}",
+                        solution.year(),
+                        solution.day()
+                    )
+                }),
+        }
+    }
+}
+
+fn read_file(path: &Path) -> Result<String, String> {
+    std::fs::read_to_string(path).map_err(|err| format!("Couldn't read {}: {err}", path.display()))
+}
+
+// Written to a temporary file first, so an interrupted write can't leave a truncated input cached
+fn write_file(path: &Path, contents: &str) -> Result<(), String> {
+    let error = |err: std::io::Error| format!("Couldn't write {}: {err}", path.display());
+    let partial = path.with_extension("txt.partial");
+
+    if let Some(directory) = path.parent() {
+        std::fs::create_dir_all(directory).map_err(error)?;
+    }
+
+    std::fs::write(&partial, contents).map_err(error)?;
+    std::fs::rename(&partial, path).map_err(error)
+}
+
+/// The real puzzle input for tests, these are kept out of the source tree. Tests never download
+/// inputs, a missing one has to be cached first.
+#[cfg(test)]
+pub fn puzzle_input(year: u16, day: u8) -> String {
+    InputProvider::offline().load(year, day).unwrap()
+}
+
+/// Like `puzzle_input`, for days whose input isn't checked in
+#[cfg(test)]
+pub fn optional_puzzle_input(year: u16, day: u8) -> Option<String> {
+    InputProvider::offline().load(year, day).ok()
+}
+
+#[cfg(test)]
+mod input_tests {
+    use crate::input::{InputProvider, InputSource};
+    use std::path::PathBuf;
+
+    #[test]
+    fn resolves_puzzle_paths() {
+        let provider = InputProvider::new("inputs");
+
+        assert_eq!(
+            provider.path(2024, 6),
+            PathBuf::from("inputs/2024/day06.txt")
+        );
+        assert_eq!(
+            provider.path(2023, 22),
+            PathBuf::from("inputs/2023/day22.txt")
+        );
+    }
+
+    #[test]
+    fn parses_sources() {
+        assert_eq!(InputSource::parse("-"), InputSource::Stdin);
+        assert_eq!(
+            InputSource::parse("my_input.txt"),
+            InputSource::File(PathBuf::from("my_input.txt"))
+        );
+    }
+
+    #[test]
+    fn resolves_fixtures() {
+        let provider = InputProvider::offline();
+        let solution = crate::find_solution(2023, 1).unwrap();
+
+        let example = provider
+            .resolve(&InputSource::Fixture("example_2".to_string()), solution)
+            .unwrap();
+
+        assert!(example.starts_with("two1nine"));
+        assert!(provider
+            .resolve(&InputSource::Fixture("example_3".to_string()), solution)
+            .is_err());
+    }
+}
@@ -2,6 +2,7 @@
 extern crate lazy_static;
 
 mod day1;
+mod day1_faster;
 mod day2;
 mod day3;
 mod day4;
@@ -11,5 +12,88 @@ mod day7;
 mod day8;
 mod day9;
 
-mod questions_2023;
-mod questions_2024;
+pub mod questions_2023;
+pub mod questions_2024;
+
+mod answers;
+mod bench;
+mod bigint;
+mod error;
+mod fetch;
+mod geometry;
+mod grid;
+mod input;
+mod search;
+mod solution;
+
+pub use answers::{Answers, ExpectedAnswer, Verdict};
+pub use bench::{
+    baseline_path, bench_solution, compare, load_baseline, save_baseline, to_csv, to_json,
+    write_report, Comparison, Measurement, ReportFormat,
+};
+pub use bigint::BigUint;
+pub use error::{ParseError, ParseErrorKind};
+pub use fetch::{InputFetcher, BASE_URL_VARIABLE, DEFAULT_BASE_URL, SESSION_VARIABLE};
+pub use geometry::{Direction, Direction8, Point, Point3};
+pub use grid::Grid;
+pub use input::{InputProvider, InputSource, INPUT_DIR_VARIABLE};
+pub use search::{astar, bfs, dijkstra, longest_path, Neighbours, Path};
+pub use solution::{Answer, Solution, Variant};
+
+static SOLUTIONS: &[&dyn Solution] = &[
+    &day1::Day1,
+    &day2::Day2,
+    &day3::Day3,
+    &day4::Day4,
+    &day5::Day5,
+    &day6::Day6,
+    &day7::Day7,
+    &day8::Day8,
+    &questions_2023::day1::Day1,
+    &questions_2023::day2::Day2,
+    &questions_2023::day3::Day3,
+    &questions_2023::day4::Day4,
+    &questions_2023::day22::Day22,
+    &questions_2023::day23::Day23,
+    &questions_2024::day2::Day2,
+    &questions_2024::day3::Day3,
+    &questions_2024::day4::Day4,
+    &questions_2024::day5::Day5,
+    &questions_2024::day6::Day6,
+    &questions_2024::day7::Day7,
+    &questions_2024::day8::Day8,
+    &questions_2024::day9::Day9,
+];
+
+/// Every implemented puzzle, ordered by year and day
+pub fn registry() -> &'static [&'static dyn Solution] {
+    SOLUTIONS
+}
+
+pub fn find_solution(year: u16, day: u8) -> Option<&'static dyn Solution> {
+    SOLUTIONS
+        .iter()
+        .find(|solution| solution.year() == year && solution.day() == day)
+        .copied()
+}
+
+#[cfg(test)]
+mod registry_tests {
+    use crate::{find_solution, registry};
+
+    #[test]
+    fn registry_is_ordered_and_unique() {
+        let keys: Vec<(u16, u8)> = registry()
+            .iter()
+            .map(|solution| (solution.year(), solution.day()))
+            .collect();
+
+        assert!(keys.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn find_solution_by_year_and_day() {
+        assert_eq!(find_solution(2024, 6).unwrap().title(), "Guard Gallivant");
+        assert!(find_solution(2024, 1).is_none());
+    }
+}
@@ -0,0 +1,399 @@
+use adventofcode::{
+    Answers, InputFetcher, InputProvider, InputSource, Measurement, Solution, Verdict,
+};
+use std::path::PathBuf;
+use std::time::Instant;
+use std::{env, process};
+
+const USAGE: &str = "Usage: adventofcode --year <year> --day <day> --part <1|2> [--input <path|->] [--fixture <name>]
+       adventofcode --render --year <year> --day <day> --part <1|2> [--input <path|->] [--fixture <name>]
+                             [--frames]
+       adventofcode --check [--year <year>] [--day <day>] [--answers <path>]
+       adventofcode --bench [--year <year>] [--day <day>] [--runs <n>] [--output <path.json|path.csv>]
+                            [--save-baseline <name>] [--baseline <name> [--threshold <percent>]]
+       adventofcode --list
+
+Puzzle inputs are read from <inputs>/<year>/day<NN>.txt, set the directory with
+--inputs <dir> or the AOC_INPUTS environment variable. Missing inputs are
+downloaded into it when AOC_SESSION holds a session cookie, AOC_BASE_URL
+changes the server they're downloaded from.";
+
+#[derive(Eq, PartialEq)]
+enum Mode {
+    Run,
+    Render,
+    Check,
+    Bench,
+    List,
+}
+
+struct Args {
+    mode: Mode,
+    year: Option<u16>,
+    day: Option<u8>,
+    part: Option<u8>,
+    input: InputSource,
+    inputs: Option<String>,
+    answers: Option<String>,
+    runs: usize,
+    output: Option<String>,
+    save_baseline: Option<String>,
+    baseline: Option<String>,
+    threshold: f64,
+    /// Render one picture per step instead of the end result
+    frames: bool,
+}
+
+impl Args {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut result = Self {
+            mode: Mode::Run,
+            year: None,
+            day: None,
+            part: None,
+            input: InputSource::Puzzle,
+            inputs: None,
+            answers: None,
+            runs: 10,
+            output: None,
+            save_baseline: None,
+            baseline: None,
+            threshold: 10.0,
+            frames: false,
+        };
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--render" => result.mode = Mode::Render,
+                "--check" => result.mode = Mode::Check,
+                "--bench" => result.mode = Mode::Bench,
+                "--list" => result.mode = Mode::List,
+                "--frames" => result.frames = true,
+                _ => {
+                    let value = args
+                        .next()
+                        .ok_or_else(|| format!("Missing value for {arg}"))?;
+
+                    match arg.as_str() {
+                        "--year" => result.year = Some(parse_number(&arg, &value)?),
+                        "--day" => result.day = Some(parse_number(&arg, &value)?),
+                        "--part" => result.part = Some(parse_number(&arg, &value)?),
+                        "--input" => result.input = InputSource::parse(&value),
+                        "--fixture" => result.input = InputSource::Fixture(value),
+                        "--inputs" => result.inputs = Some(value),
+                        "--answers" => result.answers = Some(value),
+                        "--runs" => result.runs = parse_number(&arg, &value)?,
+                        "--output" => result.output = Some(value),
+                        "--save-baseline" => result.save_baseline = Some(value),
+                        "--baseline" => result.baseline = Some(value),
+                        "--threshold" => result.threshold = parse_number(&arg, &value)?,
+                        _ => return Err(format!("Unknown argument {arg}")),
+                    }
+                }
+            }
+        }
+
+        if matches!(result.mode, Mode::Run | Mode::Render)
+            && (result.year.is_none() || result.day.is_none() || result.part.is_none())
+        {
+            return Err("Missing --year, --day or --part".to_string());
+        }
+
+        Ok(result)
+    }
+
+    fn selects(&self, solution: &dyn Solution) -> bool {
+        self.year.is_none_or(|year| solution.year() == year)
+            && self.day.is_none_or(|day| solution.day() == day)
+    }
+
+    fn provider(&self) -> InputProvider {
+        let Some(directory) = &self.inputs else {
+            return InputProvider::default();
+        };
+
+        match InputFetcher::from_env() {
+            Some(fetcher) => InputProvider::new(directory).with_fetcher(fetcher),
+            None => InputProvider::new(directory),
+        }
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(arg: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("Expected a number for {arg}, found '{value}'"))
+}
+
+fn default_answers_path() -> PathBuf {
+    [env!("CARGO_MANIFEST_DIR"), "answers.toml"]
+        .iter()
+        .collect()
+}
+
+// Timings depend on the machine, so baselines stay out of the repository
+fn baselines_dir() -> PathBuf {
+    [env!("CARGO_MANIFEST_DIR"), "target", "baselines"]
+        .iter()
+        .collect()
+}
+
+fn list() {
+    for solution in adventofcode::registry() {
+        println!(
+            "{} day {:>2}: {}",
+            solution.year(),
+            solution.day(),
+            solution.title()
+        );
+    }
+}
+
+fn run(args: &Args) -> Result<(), String> {
+    let (year, day, part) = (args.year.unwrap(), args.day.unwrap(), args.part.unwrap());
+
+    let solution = adventofcode::find_solution(year, day)
+        .ok_or_else(|| format!("{year} day {day} is not implemented"))?;
+    let input = args.provider().resolve(&args.input, solution)?;
+
+    let current_time = Instant::now();
+    let answer = solution
+        .part(part, &input)
+        .ok_or_else(|| format!("There is no part {part}"))?
+        .map_err(|err| err.to_string())?;
+    let elapsed = current_time.elapsed();
+
+    println!(
+        "{year} day {day} part {part} ({}): {answer}",
+        solution.title()
+    );
+    println!("Time: {:?}", elapsed);
+
+    Ok(())
+}
+
+fn render(args: &Args) -> Result<(), String> {
+    let (year, day, part) = (args.year.unwrap(), args.day.unwrap(), args.part.unwrap());
+
+    let solution = adventofcode::find_solution(year, day)
+        .ok_or_else(|| format!("{year} day {day} is not implemented"))?;
+    let input = args.provider().resolve(&args.input, solution)?;
+
+    if args.frames {
+        let frames = solution
+            .render_frames(part, &input)
+            .ok_or_else(|| format!("{year} day {day} part {part} has nothing to animate"))?
+            .map_err(|err| err.in_puzzle(year, day).to_string())?;
+
+        for frame in frames {
+            println!("{frame}");
+        }
+
+        return Ok(());
+    }
+
+    let picture = solution
+        .render(part, &input)
+        .ok_or_else(|| format!("{year} day {day} part {part} has nothing to render"))?
+        .map_err(|err| err.in_puzzle(year, day).to_string())?;
+
+    print!("{picture}");
+
+    Ok(())
+}
+
+fn check(args: &Args) -> Result<(), String> {
+    let answers_path = args
+        .answers
+        .as_ref()
+        .map_or_else(default_answers_path, PathBuf::from);
+    let answers = Answers::load(&answers_path)?;
+
+    let provider = args.provider();
+
+    let mut passed = 0;
+    let mut failed = 0;
+    let mut unknown = 0;
+
+    for solution in adventofcode::registry() {
+        if !args.selects(*solution) {
+            continue;
+        }
+
+        let (year, day) = (solution.year(), solution.day());
+
+        let input = match provider.load(year, day) {
+            Ok(input) => input,
+            Err(err) => {
+                println!("{year} day {day:>2}: skipped, {err}");
+                continue;
+            }
+        };
+
+        for part in 1..=2 {
+            let current_time = Instant::now();
+            let result = solution.part(part, &input).unwrap();
+            let elapsed = current_time.elapsed();
+
+            let answer = match result {
+                Ok(answer) => answer,
+                Err(err) => {
+                    failed += 1;
+                    println!("{year} day {day:>2} part {part}: error    {err}");
+                    continue;
+                }
+            };
+
+            let verdict = answers.verify(year, day, part, &answer);
+
+            match verdict {
+                Verdict::Pass => passed += 1,
+                Verdict::Unknown | Verdict::Unsolved => unknown += 1,
+                _ => failed += 1,
+            }
+
+            println!(
+                "{year} day {day:>2} part {part}: {:<8} {answer} ({elapsed:?})",
+                verdict.to_string()
+            );
+        }
+    }
+
+    println!("\nPassed {passed}, failed {failed}, unknown {unknown}");
+
+    if failed > 0 {
+        return Err(format!("{failed} answer(s) didn't match"));
+    }
+
+    Ok(())
+}
+
+fn bench(args: &Args) -> Result<(), String> {
+    let provider = args.provider();
+    let mut measurements: Vec<Measurement> = Vec::new();
+
+    for solution in adventofcode::registry() {
+        if !args.selects(*solution) {
+            continue;
+        }
+
+        let (year, day) = (solution.year(), solution.day());
+
+        let input = match provider.load(year, day) {
+            Ok(input) => input,
+            Err(err) => {
+                println!("{year} day {day:>2}: skipped, {err}");
+                continue;
+            }
+        };
+
+        let results = match adventofcode::bench_solution(*solution, &input, args.runs) {
+            Ok(results) => results,
+            Err(err) => {
+                println!("{year} day {day:>2}: error    {err}");
+                continue;
+            }
+        };
+
+        for m in &results {
+            println!(
+                "{year} day {day:>2} {:<5}: min {:>12?} median {:>12?} p95 {:>12?} {:>10.2} MB/s",
+                m.stage,
+                m.min(),
+                m.median(),
+                m.p95(),
+                m.throughput_mb_s
+            );
+        }
+
+        measurements.extend(results);
+    }
+
+    if let Some(output) = &args.output {
+        adventofcode::write_report(output.as_ref(), &measurements)?;
+        println!("\nWrote {} measurements to {output}", measurements.len());
+    }
+
+    if let Some(name) = &args.save_baseline {
+        let path = adventofcode::baseline_path(&baselines_dir(), name);
+        adventofcode::save_baseline(&path, &measurements)?;
+        println!("\nSaved baseline {name} to {}", path.display());
+    }
+
+    if let Some(name) = &args.baseline {
+        let path = adventofcode::baseline_path(&baselines_dir(), name);
+        let baseline = adventofcode::load_baseline(&path)?;
+
+        return compare(&measurements, &baseline, name, args.threshold);
+    }
+
+    Ok(())
+}
+
+fn compare(
+    measurements: &[Measurement],
+    baseline: &[Measurement],
+    name: &str,
+    threshold: f64,
+) -> Result<(), String> {
+    println!("\nCompared to baseline {name}, regressions are over {threshold}%:");
+
+    let mut regressed = 0;
+
+    for comparison in adventofcode::compare(measurements, baseline) {
+        let m = comparison.current;
+        let label = format!("{} day {:>2} {:<5}", m.year, m.day, m.stage);
+
+        let (Some(before), Some(change)) = (comparison.baseline, comparison.change()) else {
+            println!("{label}: {:>12?} (not in baseline)", m.median());
+            continue;
+        };
+
+        print!(
+            "{label}: {:>12?} -> {:>12?} {change:>+8.1}%",
+            before.median(),
+            m.median()
+        );
+
+        if comparison.regressed(threshold) {
+            regressed += 1;
+            print!(" REGRESSED");
+        }
+
+        println!();
+    }
+
+    if regressed > 0 {
+        return Err(format!(
+            "{regressed} stage(s) regressed by more than {threshold}% against {name}"
+        ));
+    }
+
+    Ok(())
+}
+
+fn main() {
+    let args = match Args::parse(env::args().skip(1)) {
+        Ok(args) => args,
+        Err(err) => {
+            eprintln!("{err}\n{USAGE}");
+            process::exit(1);
+        }
+    };
+
+    let result = match args.mode {
+        Mode::Run => run(&args),
+        Mode::Render => render(&args),
+        Mode::Check => check(&args),
+        Mode::Bench => bench(&args),
+        Mode::List => {
+            list();
+            Ok(())
+        }
+    };
+
+    if let Err(err) = result {
+        eprintln!("{err}");
+        process::exit(1);
+    }
+}
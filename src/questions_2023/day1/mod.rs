@@ -1,8 +1,8 @@
+use crate::error::{ParseError, ParseErrorKind};
+use crate::solution::{Answer, Solution};
 use lazy_static::lazy_static;
 
 use std::collections::HashMap;
-use std::io::Read;
-use std::str::from_utf8;
 
 lazy_static! {
     static ref HASHMAP: HashMap<&'static str, u32> = {
@@ -75,76 +75,122 @@ fn get_spelled<T: AsRef<str>>(input: T) -> Option<u32> {
 //         .collect()
 // }
 
-fn get_numbers(line: &str) -> Vec<u32> {
-    line.chars()
-        .enumerate()
-        .filter_map(|(index, _)| {
-            let possible_word = &line[index..];
-
-            // Check if the slice has at least one character and the first character is numeric
-            if let Some(first_char) = possible_word.chars().next() {
-                if first_char.is_numeric() {
-                    return Some(first_char.to_digit(10).unwrap());
-                }
+fn get_numbers(line: &str, include_spelled: bool) -> Vec<u32> {
+    line.char_indices()
+        .filter_map(|(index, first_char)| {
+            if let Some(digit) = first_char.to_digit(10) {
+                return Some(digit);
             }
 
-            if let Some(val) = get_spelled(possible_word) {
-                return Some(val);
+            if !include_spelled {
+                return None;
             }
 
-            return None;
+            get_spelled(&line[index..])
         })
         .collect()
 }
 
-fn calculate(input: &str) -> usize {
-    input.split("\n").fold(0, |acc, line| {
-        let numbers = get_numbers(&line);
+/// The first and last digit of every line
+fn parse_calibration_values(
+    input: &str,
+    include_spelled: bool,
+) -> Result<Vec<(u32, u32)>, ParseError> {
+    input
+        .split("\n")
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let numbers = get_numbers(line, include_spelled);
+
+            match (numbers.first(), numbers.last()) {
+                (Some(first), Some(last)) => Ok((*first, *last)),
+                _ => Err(ParseError::new(
+                    input,
+                    line,
+                    ParseErrorKind::MissingField("calibration digit"),
+                )),
+            }
+        })
+        .collect()
+}
 
-        let mut total = 0;
+pub fn calculate(input: &str, include_spelled: bool) -> Result<usize, ParseError> {
+    Ok(parse_calibration_values(input, include_spelled)?
+        .into_iter()
+        .map(|(first, last)| (first * 10 + last) as usize)
+        .sum())
+}
 
-        if numbers.len() == 0 {
-            return acc;
-        }
+pub struct Day1;
 
-        if numbers.len() == 1 {
-            let num = numbers.get(0).unwrap();
-            total = format!("{}{}", num, num).parse::<usize>().unwrap();
-        } else if numbers.len() > 1 {
-            let num1 = numbers.get(0).unwrap();
-            let num2 = numbers.get(numbers.len() - 1).unwrap();
+impl Solution for Day1 {
+    fn year(&self) -> u16 {
+        2023
+    }
 
-            total = format!("{}{}", num1, num2).parse::<usize>().unwrap();
-        }
+    fn day(&self) -> u8 {
+        1
+    }
 
-        acc + total
-    })
+    fn title(&self) -> &'static str {
+        "Trebuchet?!"
+    }
+
+    fn fixtures(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("example", include_str!("test_input.txt")),
+            ("example_2", include_str!("test_input_2.txt")),
+        ]
+    }
+
+    /// Reads spelled out digits too, so the part 2 example parses as well
+    fn parse(&self, input: &str) -> Option<Result<(), ParseError>> {
+        Some(parse_calibration_values(input, true).map(drop))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, ParseError> {
+        Ok(calculate(input, false)?.into())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, ParseError> {
+        Ok(calculate(input, true)?.into())
+    }
 }
 
 #[cfg(test)]
 mod day1_tests {
+    use crate::input::puzzle_input;
     use crate::questions_2023::day1::calculate;
 
     #[test]
     fn test_input_challenge_1() {
         let input = include_str!("test_input.txt");
-        let result = calculate(input);
+        let result = calculate(input, false).unwrap();
 
         assert_eq!(result, 142);
     }
 
     #[test]
     fn input_challenge_1() {
-        let input = include_str!("input.txt");
-        let result = calculate(input);
+        let input = &puzzle_input(2023, 1);
+        let result = calculate(input, false).unwrap();
 
         println!("{}", result)
     }
 
+    #[test]
+    fn reads_any_numeral_without_panicking() {
+        assert_eq!(calculate("a\u{663}b1\n", false).unwrap(), 11);
+
+        let error = calculate("12\nabc\n", false).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.found, "abc");
+    }
+
     #[test]
     fn test_input_challenge_2() {
         let input = include_str!("test_input_2.txt");
-        let result = calculate(input);
+        let result = calculate(input, true).unwrap();
 
         assert_eq!(result, 281);
     }
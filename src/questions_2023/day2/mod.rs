@@ -1,85 +1,126 @@
+use crate::error::{expect_field, parse_integer, ParseError, ParseErrorKind};
+use crate::solution::{Answer, Solution};
+
 const MAX_RED: usize = 12;
 const MAX_GREEN: usize = 13;
 const MAX_BLUE: usize = 14;
 
-fn calculate(input: &str) -> (usize, usize) {
+/// The most cubes of each color shown at once during a game
+struct Game {
+    id: usize,
+    red: usize,
+    green: usize,
+    blue: usize,
+}
+
+fn parse_games(input: &str) -> Result<Vec<Game>, ParseError> {
     input
         .split("\n")
-        .into_iter()
         .filter(|line| !line.is_empty())
-        .filter_map(|line| {
+        .map(|line| {
             let mut split = line.split(": ");
 
-            let x = split.next().unwrap();
-            let mut game_id = x.replace("Game ", "").parse::<usize>().unwrap();
-
-            let next = split.next().unwrap();
+            let x = expect_field(input, line, split.next(), "game id")?;
+            let mut game = Game {
+                id: parse_integer(input, x.trim_start_matches("Game "))?,
+                red: 0,
+                green: 0,
+                blue: 0,
+            };
 
-            let mut min_red = 0;
-            let mut min_green = 0;
-            let mut min_blue = 0;
+            let next = expect_field(input, line, split.next(), "cube sets")?;
 
             for set in next.split("; ") {
                 for hand in set.split(", ") {
                     let mut hand = hand.split(' ');
-                    let total = hand.next().unwrap().parse::<usize>().unwrap();
-                    let color = hand.next().unwrap();
-
-                    match color {
-                        "red" => {
-                            if total > min_red {
-                                min_red = total;
-                            }
-                            if total > MAX_RED {
-                                game_id = 0;
-                            }
-                        }
-                        "green" => {
-                            if total > min_green {
-                                min_green = total;
-                            }
-                            if total > MAX_GREEN {
-                                game_id = 0;
-                            }
-                        }
-                        "blue" => {
-                            if total > min_blue {
-                                min_blue = total;
-                            }
-                            if total > MAX_BLUE {
-                                game_id = 0;
-                            }
-                        }
+                    let total = parse_integer::<usize>(input, hand.next().unwrap())?;
+                    let color = expect_field(input, line, hand.next(), "color")?;
+
+                    let most = match color {
+                        "red" => &mut game.red,
+                        "green" => &mut game.green,
+                        "blue" => &mut game.blue,
                         _ => {
-                            panic!("Unknown color");
+                            return Err(ParseError::new(
+                                input,
+                                color,
+                                ParseErrorKind::UnknownToken("color"),
+                            ));
                         }
-                    }
+                    };
+
+                    *most = total.max(*most);
                 }
             }
 
-            Some((game_id, min_blue * min_green * min_red))
-        })
-        .fold((0, 0), |(game_ids, minimums), (game_id, minimum)| {
-            (game_ids + game_id, minimums + minimum)
+            Ok(game)
         })
+        .collect()
+}
+
+pub fn calculate(input: &str) -> Result<(usize, usize), ParseError> {
+    Ok(parse_games(input)?
+        .iter()
+        .fold((0, 0), |(game_ids, minimums), game| {
+            let possible = game.red <= MAX_RED && game.green <= MAX_GREEN && game.blue <= MAX_BLUE;
+            let game_id = if possible { game.id } else { 0 };
+
+            (
+                game_ids + game_id,
+                minimums + game.red * game.green * game.blue,
+            )
+        }))
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    fn year(&self) -> u16 {
+        2023
+    }
+
+    fn day(&self) -> u8 {
+        2
+    }
+
+    fn title(&self) -> &'static str {
+        "Cube Conundrum"
+    }
+
+    fn fixtures(&self) -> &'static [(&'static str, &'static str)] {
+        &[("example", include_str!("test_input.txt"))]
+    }
+
+    fn parse(&self, input: &str) -> Option<Result<(), ParseError>> {
+        Some(parse_games(input).map(drop))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, ParseError> {
+        Ok(calculate(input)?.0.into())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, ParseError> {
+        Ok(calculate(input)?.1.into())
+    }
 }
 
 #[cfg(test)]
 mod day1_tests {
+    use crate::input::puzzle_input;
     use crate::questions_2023::day2::calculate;
 
     #[test]
     fn test_input_challenge_1() {
         let input = include_str!("test_input.txt");
-        let result = calculate(input);
+        let result = calculate(input).unwrap();
 
         assert_eq!(result, (8, 2286));
     }
 
     #[test]
     fn input_challenge_1() {
-        let input = include_str!("input.txt");
-        let result = calculate(input);
+        let input = &puzzle_input(2023, 2);
+        let result = calculate(input).unwrap();
 
         println!("{:?}", result)
     }
@@ -87,7 +128,7 @@ mod day1_tests {
     // #[test]
     // fn test_input_challenge_2() {
     //     let input = include_str!("test_input_2.txt");
-    //     let result = calculate(input);
+    //     let result = calculate(input).unwrap();
     //
     //     assert_eq!(result, 281);
     // }
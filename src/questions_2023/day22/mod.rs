@@ -1,302 +1,587 @@
-use std::collections::HashSet;
-
-#[derive(Debug, Clone, Copy)]
-struct Coord {
-    x: usize,
-    y: usize,
-    z: usize,
-}
-
-impl Coord {
-    fn new(input: &str) -> Self {
-        let mut splitted = input.split(",");
-
-        Coord {
-            x: splitted.next().unwrap().parse().unwrap(),
-            y: splitted.next().unwrap().parse().unwrap(),
-            z: splitted.next().unwrap().parse().unwrap(),
-        }
+use crate::error::{expect_field, parse_integer, ParseError, ParseErrorKind};
+use crate::geometry::Point3;
+use crate::grid::Grid;
+use crate::solution::{Answer, Solution};
+
+fn parse_coord(input: &str, coord: &str) -> Result<Point3, ParseError> {
+    let mut splitted = coord.split(",");
+    let mut next = |name| -> Result<isize, ParseError> {
+        let field = expect_field(input, coord, splitted.next(), name)?;
+
+        Ok(parse_integer::<usize>(input, field)? as isize)
+    };
+
+    let (x, y) = (next("x")?, next("y")?);
+    let z_field = expect_field(input, coord, splitted.next(), "z")?;
+    let z = parse_integer::<usize>(input, z_field)? as isize;
+
+    // The ground is at z 0, so the lowest a brick can be is 1
+    if z < 1 {
+        return Err(ParseError::new(
+            input,
+            z_field,
+            ParseErrorKind::Invalid("bricks can't be below z 1"),
+        ));
     }
 
-    fn filter_vertical_coords_for_falling_block(coords: Vec<Coord>) -> Vec<Coord> {
-        // Do all coords have the same x and y?
-        let same_x_and_y = coords
-            .iter()
-            .all(|coord| coord.x == coords[0].x && coord.y == coords[0].y);
-
-        if same_x_and_y {
-            let min_z = coords.iter().map(|coord| coord.z).min().unwrap();
-
-            vec![Coord {
-                x: coords[0].x,
-                y: coords[0].y,
-                z: min_z,
-            }]
-        } else {
-            coords
-        }
-    }
+    Ok(Point3::new(x, y, z))
+}
 
-    fn get_coords_between_positions(coord1: &Coord, coord2: &Coord) -> Vec<Coord> {
-        let mut coords: Vec<Coord> = Vec::new();
+fn get_coords_between_positions(coord1: &Point3, coord2: &Point3) -> Vec<Point3> {
+    let mut coords: Vec<Point3> = Vec::new();
 
-        for x in coord1.x..=coord2.x {
-            for y in coord1.y..=coord2.y {
-                for z in coord1.z..=coord2.z {
-                    coords.push(Coord { x, y, z });
-                }
+    for x in coord1.x..=coord2.x {
+        for y in coord1.y..=coord2.y {
+            for z in coord1.z..=coord2.z {
+                coords.push(Point3::new(x, y, z));
             }
         }
-
-        coords
     }
+
+    coords
 }
 
-#[derive(Debug, Clone, Copy)]
-struct Brick {
-    from: Coord,
-    to: Coord,
-    brick_number: usize,
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Brick {
+    pub from: Point3,
+    pub to: Point3,
+    /// The line of the input the brick is on, counting from 0
+    pub brick_number: usize,
 }
 
 impl Brick {
-    fn new(line: &str, line_number: usize) -> Self {
+    fn new(input: &str, line: &str, line_number: usize) -> Result<Self, ParseError> {
         let mut splitted = line.split("~");
 
         let from = splitted.next().unwrap();
-        let to = splitted.next().unwrap();
+        let to = expect_field(input, line, splitted.next(), "end coordinate")?;
 
-        Brick {
-            from: Coord::new(from),
-            to: Coord::new(to),
+        let brick = Brick {
+            from: parse_coord(input, from)?,
+            to: parse_coord(input, to)?,
             brick_number: line_number,
+        };
+
+        // Cubes are listed from `from` up to `to`, a reversed brick would have none
+        if brick.from.x > brick.to.x || brick.from.y > brick.to.y || brick.from.z > brick.to.z {
+            return Err(ParseError::new(
+                input,
+                to,
+                ParseErrorKind::Invalid("end coordinate before the start"),
+            ));
         }
+
+        Ok(brick)
     }
 }
 
-#[derive(Debug)]
-struct Map {
-    map: Vec<Vec<Vec<bool>>>,
-}
+struct Map;
 
 impl Map {
-    fn get_bricks_for_input(input: &str) -> Vec<Brick> {
+    fn get_bricks_for_input(input: &str) -> Result<Vec<Brick>, ParseError> {
         input
             .split("\n")
             .enumerate()
             .filter(|(_, line)| !line.is_empty())
-            .map(|(index, line)| Brick::new(line, index))
+            .map(|(index, line)| Brick::new(input, line, index))
             .collect()
     }
+}
 
-    fn create_map_from_bricks(bricks: &Vec<Brick>) -> Self {
-        let mut map: Vec<Vec<Vec<bool>>> = Vec::new();
-
-        bricks.iter().for_each(|brick| {
-            let coords = Coord::get_coords_between_positions(&brick.from, &brick.to);
-
-            coords.iter().for_each(|coord| {
-                let x = coord.x;
-                let y = coord.y;
-                let z = coord.z;
-
-                if map.len() <= x {
-                    map.resize(x + 1, Vec::new());
-                }
-
-                if map[x].len() <= y {
-                    map[x].resize(y + 1, Vec::new());
-                }
-
-                if map[x][y].len() <= z {
-                    map[x][y].resize(z + 1, false);
-                }
+/// Which bricks rest on which, by index into `Simulator::bricks`
+#[derive(Debug, Default, Clone)]
+struct SupportGraph {
+    /// Per brick, the bricks resting directly on top of it
+    supports: Vec<Vec<usize>>,
+    /// Per brick, the bricks it rests on directly, empty for bricks on the ground
+    supported_by: Vec<Vec<usize>>,
+    /// Per brick, its parent in the dominator tree and how far below the ground it is in the tree
+    ///
+    /// The parent is the highest brick every route from the ground up to the brick goes through,
+    /// `None` when that's the ground itself.
+    dominators: Vec<(Option<usize>, usize)>,
+}
 
-                map[x][y][z] = true;
-            });
-        });
+impl SupportGraph {
+    /// Needs the bricks ordered so every brick comes after the bricks it rests on
+    fn find_dominators(&mut self) {
+        let mut dominators: Vec<(Option<usize>, usize)> =
+            Vec::with_capacity(self.supported_by.len());
+
+        let depth = |dominators: &[(Option<usize>, usize)], brick: Option<usize>| {
+            brick.map_or(0, |brick| dominators[brick].1)
+        };
+
+        for supporters in &self.supported_by {
+            let dominator = supporters
+                .iter()
+                .map(|supporter| Some(*supporter))
+                .reduce(|mut a, mut b| {
+                    while a != b {
+                        if depth(&dominators, a) < depth(&dominators, b) {
+                            b = dominators[b.unwrap()].0;
+                        } else {
+                            a = dominators[a.unwrap()].0;
+                        }
+                    }
+                    a
+                })
+                .flatten();
 
-        Self { map }
-    }
+            dominators.push((dominator, depth(&dominators, dominator) + 1));
+        }
 
-    fn exists_brick_at_coord(&self, coord: &Coord) -> bool {
-        self.map[coord.x][coord.y][coord.z]
+        self.dominators = dominators;
     }
 }
 
+/// The pile of bricks, the queries about which brick rests on which need `simulate_falling` first
 #[derive(Debug)]
-struct Simulator {
+pub struct Simulator {
     bricks: Vec<Brick>,
+    graph: SupportGraph,
 }
 
 impl Simulator {
-    fn new(bricks: Vec<Brick>) -> Self {
-        Self { bricks }
+    pub fn new(bricks: Vec<Brick>) -> Self {
+        Self {
+            bricks,
+            graph: SupportGraph::default(),
+        }
     }
 
-    // All updated bricks, and the bricks that fell
-    fn simulate_falling_step(bricks: Vec<Brick>) -> (Vec<Brick>, Vec<Brick>) {
-        let map = Map::create_map_from_bricks(&bricks);
-        let mut fallen_bricks = Vec::new();
+    pub fn from_input(input: &str) -> Result<Self, ParseError> {
+        Ok(Self::new(Map::get_bricks_for_input(input)?))
+    }
 
-        let updated_bricks = bricks
-            .clone()
-            .into_iter()
-            .map(|brick| {
-                if brick.from.z == 1 || brick.to.z == 1 {
-                    return brick;
-                }
+    pub fn bricks(&self) -> &[Brick] {
+        &self.bricks
+    }
 
-                let mut fallen_brick = brick.clone();
+    fn index_of(&self, brick_number: usize) -> Option<usize> {
+        self.bricks
+            .iter()
+            .position(|brick| brick.brick_number == brick_number)
+    }
 
-                fallen_brick.from.z -= 1;
-                fallen_brick.to.z -= 1;
+    fn brick_numbers(&self, indices: impl IntoIterator<Item = usize>) -> Vec<usize> {
+        indices
+            .into_iter()
+            .map(|index| self.bricks[index].brick_number)
+            .collect()
+    }
 
-                let coords = Coord::filter_vertical_coords_for_falling_block(
-                    Coord::get_coords_between_positions(&fallen_brick.from, &fallen_brick.to),
-                );
+    /// The bricks brick `brick_number` rests on directly
+    pub fn supporters(&self, brick_number: usize) -> Option<Vec<usize>> {
+        let supporters = self.graph.supported_by.get(self.index_of(brick_number)?)?;
 
-                let can_fall = coords.iter().all(|coord| !map.exists_brick_at_coord(coord));
+        Some(self.brick_numbers(supporters.iter().copied()))
+    }
 
-                if can_fall {
-                    // println!("Can fall: {:?}", brick);
-                    fallen_bricks.push(brick);
-                    fallen_brick
-                } else {
-                    // println!("Cannot fall: {:?}", brick);
-                    brick
-                }
-            })
-            .collect();
+    /// The bricks resting directly on brick `brick_number`
+    pub fn supported(&self, brick_number: usize) -> Option<Vec<usize>> {
+        let supported = self.graph.supports.get(self.index_of(brick_number)?)?;
 
-        (updated_bricks, fallen_bricks)
+        Some(self.brick_numbers(supported.iter().copied()))
     }
 
-    fn simulate_falling(&mut self) {
-        loop {
-            let (updated_bricks, fallen_bricks) = Self::simulate_falling_step(self.bricks.clone());
+    /// Every brick that falls in the chain reaction after brick `brick_number` is removed
+    pub fn would_fall(&self, brick_number: usize) -> Option<Vec<usize>> {
+        let removed = self.index_of(brick_number)?;
+        self.graph.dominators.get(removed)?;
 
-            if fallen_bricks.len() == 0 {
-                break;
-            }
+        // Dominators always come before the bricks they dominate
+        let mut falls = vec![false; self.bricks.len()];
+        falls[removed] = true;
 
-            self.bricks = updated_bricks;
+        for brick in removed + 1..self.bricks.len() {
+            if let (Some(dominator), _) = self.graph.dominators[brick] {
+                falls[brick] = falls[dominator];
+            }
         }
+        falls[removed] = false;
+
+        Some(self.brick_numbers((0..self.bricks.len()).filter(|brick| falls[*brick])))
     }
 
-    fn get_total_fall_count(&self) -> usize {
-        let removable_brick_count = self
+    fn is_safe_to_disintegrate(&self, index: usize) -> bool {
+        self.graph.supports[index]
+            .iter()
+            .all(|above| self.graph.supported_by[*above].len() > 1)
+    }
+
+    /// The bricks that can be removed without any other brick falling
+    pub fn safe_to_disintegrate(&self) -> Vec<usize> {
+        let indices =
+            (0..self.graph.supports.len()).filter(|index| self.is_safe_to_disintegrate(*index));
+
+        self.brick_numbers(indices)
+    }
+
+    /// Side view of the pile looking along the y axis, like the pictures in the puzzle
+    pub fn render_xz(&self) -> String {
+        self.render_projection(|coord| coord.x)
+    }
+
+    /// Side view of the pile looking along the x axis, like the pictures in the puzzle
+    pub fn render_yz(&self) -> String {
+        self.render_projection(|coord| coord.y)
+    }
+
+    /// Every cube is labelled with its brick's number, `?` when several bricks are in line and
+    /// `.` when there's none. Each row ends with its height, the ground is at 0.
+    fn render_projection(&self, horizontal: impl Fn(&Point3) -> isize) -> String {
+        let width = self
             .bricks
             .iter()
-            .enumerate()
-            .map(|(index, brick)| {
-                let mut bricks_without_this_brick: Vec<Brick> = self
-                    .bricks
-                    .clone()
-                    .into_iter()
-                    .enumerate()
-                    .filter(|(other_index, _)| index != *other_index)
-                    .map(|(_, brick)| brick)
-                    .collect();
-
-                let mut unique_fallen_bricks: HashSet<usize> = HashSet::new();
-
-                loop {
-                    // println!("====\nSimulation step for brick {:?}\n====", brick);
-
-                    let (updated_bricks, fallen_bricks) =
-                        Self::simulate_falling_step(bricks_without_this_brick);
-
-                    if fallen_bricks.len() == 0 {
-                        break;
-                    }
+            .map(|brick| horizontal(&brick.to) + 1)
+            .max()
+            .unwrap_or(0);
+        let top = self
+            .bricks
+            .iter()
+            .map(|brick| brick.to.z)
+            .max()
+            .unwrap_or(0);
+        let label_width = self
+            .bricks
+            .iter()
+            .map(|brick| brick.brick_number.to_string().len())
+            .max()
+            .unwrap_or(1);
+
+        let mut cells: Grid<Vec<usize>> = Grid::new(width as usize, top as usize, Vec::new());
 
-                    bricks_without_this_brick = updated_bricks;
-                    fallen_bricks.iter().for_each(|brick| {
-                        unique_fallen_bricks.insert(brick.brick_number);
-                    });
-
-                    // println!(
-                    //     "Total fallen so far: {} {:?}",
-                    //     unique_fallen_bricks.len(),
-                    //     brick
-                    // );
-                    // println!("====\nEnd\n====");
+        for brick in &self.bricks {
+            for coord in get_coords_between_positions(&brick.from, &brick.to) {
+                let cell = &mut cells[(horizontal(&coord) as usize, (top - coord.z) as usize)];
+
+                if !cell.contains(&brick.brick_number) {
+                    cell.push(brick.brick_number);
                 }
+            }
+        }
 
-                unique_fallen_bricks.len()
-            })
-            .sum();
+        // Multi digit labels need a gap to tell neighbouring bricks apart
+        let separator = if label_width > 1 { " " } else { "" };
+        let mut picture = String::new();
+
+        for (row, z) in cells.rows().zip((1..=top).rev()) {
+            let labels: Vec<String> = row
+                .iter()
+                .map(|cell| {
+                    let label = match cell.as_slice() {
+                        [] => ".".to_string(),
+                        [brick_number] => brick_number.to_string(),
+                        _ => "?".to_string(),
+                    };
+                    format!("{label:>label_width$}")
+                })
+                .collect();
+
+            picture.push_str(&format!("{} {z}\n", labels.join(separator)));
+        }
 
-        removable_brick_count
+        let ground_width = width as usize * (label_width + separator.len()) - separator.len();
+        picture.push_str(&format!("{} 0\n", "-".repeat(ground_width)));
+        picture
     }
-    fn get_removable_brick_count(&self) -> usize {
-        let removable_brick_count = self
+
+    /// The support graph in Graphviz DOT, with an edge from every brick to the bricks resting on it
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph bricks {\n");
+
+        for brick in &self.bricks {
+            let (from, to) = (brick.from, brick.to);
+            dot.push_str(&format!(
+                "    {} [label=\"{}: {},{},{}~{},{},{}\"];\n",
+                brick.brick_number, brick.brick_number, from.x, from.y, from.z, to.x, to.y, to.z
+            ));
+        }
+
+        for (below, supported) in self.graph.supports.iter().enumerate() {
+            for above in supported {
+                dot.push_str(&format!(
+                    "    {} -> {};\n",
+                    self.bricks[below].brick_number, self.bricks[*above].brick_number
+                ));
+            }
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Drops the bricks from the lowest up, each lands straight on the tallest column below it
+    ///
+    /// Afterwards the bricks are ordered by their original height, so every brick comes after
+    /// the bricks it rests on.
+    pub fn simulate_falling(&mut self) {
+        self.bricks
+            .sort_by_key(|brick| (brick.from.z, brick.brick_number));
+
+        let width = self
             .bricks
             .iter()
-            .enumerate()
-            .filter(|(index, _)| {
-                let bricks_without_this_brick: Vec<Brick> = self
-                    .bricks
-                    .clone()
-                    .into_iter()
-                    .enumerate()
-                    .filter(|(other_index, _)| *index != *other_index)
-                    .map(|(_, brick)| brick)
-                    .collect();
+            .map(|brick| brick.to.x + 1)
+            .max()
+            .unwrap_or(0);
+        let depth = self
+            .bricks
+            .iter()
+            .map(|brick| brick.to.y + 1)
+            .max()
+            .unwrap_or(0);
+
+        // Per column, the height of the top cube and the brick it belongs to
+        let mut heights: Grid<(isize, Option<usize>)> =
+            Grid::new(width as usize, depth as usize, (0, None));
+        let mut graph = SupportGraph {
+            supports: vec![Vec::new(); self.bricks.len()],
+            supported_by: vec![Vec::new(); self.bricks.len()],
+            dominators: Vec::new(),
+        };
+
+        for (index, brick) in self.bricks.iter_mut().enumerate() {
+            let footprint: Vec<(usize, usize)> = get_coords_between_positions(
+                &brick.from,
+                &Point3::new(brick.to.x, brick.to.y, brick.from.z),
+            )
+            .iter()
+            .map(|coord| (coord.x as usize, coord.y as usize))
+            .collect();
 
-                let fallen_bricks = Self::simulate_falling_step(bricks_without_this_brick).1;
+            let landing_height = footprint
+                .iter()
+                .map(|column| heights[*column].0)
+                .max()
+                .unwrap_or(0);
+
+            for column in &footprint {
+                if let (height, Some(below)) = heights[*column] {
+                    if height == landing_height && !graph.supported_by[index].contains(&below) {
+                        graph.supported_by[index].push(below);
+                        graph.supports[below].push(index);
+                    }
+                }
+            }
+
+            let fall = brick.from.z - (landing_height + 1);
+            brick.from.z -= fall;
+            brick.to.z -= fall;
 
-                fallen_bricks.len() == 0
-            })
-            .count();
+            for column in footprint {
+                heights[column] = (brick.to.z, Some(index));
+            }
+        }
 
-        removable_brick_count
+        graph.find_dominators();
+        self.graph = graph;
     }
-}
 
-fn calculate(input: &str) -> (usize, usize) {
-    let bricks = Map::get_bricks_for_input(input);
+    /// Bricks can be removed when every brick on top of them rests on another brick as well
+    fn get_removable_brick_count(&self) -> usize {
+        (0..self.bricks.len())
+            .filter(|index| self.is_safe_to_disintegrate(*index))
+            .count()
+    }
 
-    let mut simulator = Simulator::new(bricks);
+    /// Sums up how many other bricks fall when each brick is removed
+    ///
+    /// A brick falls exactly when one of its ancestors in the dominator tree is removed, so the
+    /// total is the sum of how many ancestors each brick has.
+    fn get_total_fall_count(&self) -> usize {
+        self.graph
+            .dominators
+            .iter()
+            .map(|(_, depth)| depth - 1)
+            .sum()
+    }
+}
+
+pub fn calculate(input: &str) -> Result<(usize, usize), ParseError> {
+    let mut simulator = Simulator::from_input(input)?;
 
     simulator.simulate_falling();
 
-    (
+    Ok((
         simulator.get_removable_brick_count(),
         simulator.get_total_fall_count(),
-    )
+    ))
+}
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    fn year(&self) -> u16 {
+        2023
+    }
+
+    fn day(&self) -> u8 {
+        22
+    }
+
+    fn title(&self) -> &'static str {
+        "Sand Slabs"
+    }
+
+    fn fixtures(&self) -> &'static [(&'static str, &'static str)] {
+        &[("example", include_str!("test_input.txt"))]
+    }
+
+    fn parse(&self, input: &str) -> Option<Result<(), ParseError>> {
+        Some(Map::get_bricks_for_input(input).map(drop))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, ParseError> {
+        Ok(calculate(input)?.0.into())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, ParseError> {
+        Ok(calculate(input)?.1.into())
+    }
+
+    /// Both parts look at the same settled pile
+    fn render(&self, _part: u8, input: &str) -> Option<Result<String, ParseError>> {
+        Some(Simulator::from_input(input).map(|mut simulator| {
+            simulator.simulate_falling();
+
+            format!(
+                "x-z\n{}\ny-z\n{}",
+                simulator.render_xz(),
+                simulator.render_yz()
+            )
+        }))
+    }
 }
 
 #[cfg(test)]
 mod day1_tests {
-    use crate::questions_2023::day22::{calculate, Coord, Map};
+    use crate::input::puzzle_input;
+    use crate::questions_2023::day22::{calculate, get_coords_between_positions, Map, Simulator};
 
     #[test]
     fn test_input_challenge() {
         let input = include_str!("test_input.txt");
-        let result = calculate(input);
+        let result = calculate(input).unwrap();
 
         assert_eq!(result, (5, 7));
     }
 
     #[test]
     fn input_challenge() {
-        let input = include_str!("input.txt");
-        let result = calculate(input);
+        let input = &puzzle_input(2023, 22);
+        let result = calculate(input).unwrap();
 
         println!("Challenge 0: {}, challenge 1: {}", result.0, result.1)
     }
 
+    #[test]
+    fn settles_and_links_bricks() {
+        let input = include_str!("test_input.txt");
+        let mut simulator = Simulator::new(Map::get_bricks_for_input(input).unwrap());
+
+        simulator.simulate_falling();
+
+        let heights: Vec<_> = simulator.bricks.iter().map(|brick| brick.from.z).collect();
+        assert_eq!(heights, [1, 2, 2, 3, 3, 4, 5]);
+
+        // A holds up B and C, which both hold up D and E, which both hold up F, which holds up G
+        assert_eq!(simulator.graph.supports[0], [1, 2]);
+        assert_eq!(simulator.graph.supported_by[3], [1, 2]);
+        assert_eq!(simulator.graph.supported_by[6], [5]);
+        assert!(simulator.graph.supported_by[0].is_empty());
+    }
+
+    #[test]
+    fn queries_the_settled_pile() {
+        let input = include_str!("test_input.txt");
+        let mut simulator = Simulator::from_input(input).unwrap();
+
+        assert_eq!(simulator.supporters(3), None);
+
+        simulator.simulate_falling();
+
+        // The bricks are called A to G in the puzzle, brick 3 is D
+        assert_eq!(simulator.supporters(3), Some(vec![1, 2]));
+        assert_eq!(simulator.supported(0), Some(vec![1, 2]));
+        assert_eq!(simulator.supported(6), Some(vec![]));
+        assert_eq!(simulator.would_fall(0), Some(vec![1, 2, 3, 4, 5, 6]));
+        assert_eq!(simulator.would_fall(5), Some(vec![6]));
+        assert_eq!(simulator.would_fall(1), Some(vec![]));
+        assert_eq!(simulator.would_fall(7), None);
+        assert_eq!(simulator.safe_to_disintegrate(), [1, 2, 3, 4, 6]);
+    }
+
+    #[test]
+    fn exports_dot() {
+        let input = include_str!("test_input.txt");
+        let mut simulator = Simulator::from_input(input).unwrap();
+        simulator.simulate_falling();
+
+        let dot = simulator.to_dot();
+
+        assert!(dot.starts_with("digraph bricks {\n    0 [label=\"0: 1,0,1~1,2,1\"];\n"));
+        assert!(dot.contains("    5 -> 6;\n"));
+        assert_eq!(dot.matches(" -> ").count(), 9);
+    }
+
+    #[test]
+    fn renders_side_views() {
+        let input = include_str!("test_input.txt");
+        let mut simulator = Simulator::from_input(input).unwrap();
+
+        // The pictures from the puzzle, with A to G numbered 0 to 6
+        assert_eq!(
+            simulator.render_xz(),
+            ".6. 9\n.6. 8\n... 7\n555 6\n..4 5\n3.. 4\n222 3\n111 2\n.0. 1\n--- 0\n"
+        );
+        assert_eq!(
+            simulator.render_yz(),
+            ".6. 9\n.6. 8\n... 7\n.5. 6\n444 5\n333 4\n..2 3\n1.. 2\n000 1\n--- 0\n"
+        );
+
+        simulator.simulate_falling();
+
+        assert_eq!(
+            simulator.render_xz(),
+            ".6. 6\n.6. 5\n555 4\n3.4 3\n??? 2\n.0. 1\n--- 0\n"
+        );
+        assert_eq!(
+            simulator.render_yz(),
+            ".6. 6\n.6. 5\n.5. 4\n??? 3\n1.2 2\n000 1\n--- 0\n"
+        );
+    }
+
     #[test]
     fn get_coords() {
         let input = include_str!("test_input.txt");
-        let bricks = Map::get_bricks_for_input(input);
+        let bricks = Map::get_bricks_for_input(input).unwrap();
 
         let brick = &bricks[0];
 
-        let coords = Coord::get_coords_between_positions(&brick.from, &brick.to);
+        let coords = get_coords_between_positions(&brick.from, &brick.to);
 
         assert_eq!(coords.len(), 3);
     }
+
+    #[test]
+    fn reports_bricks_below_the_ground() {
+        let error = Map::get_bricks_for_input("1,0,1~1,2,1\n0,0,0~2,0,1\n").unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 5: bricks can't be below z 1, found '0'"
+        );
+    }
+
+    #[test]
+    fn reports_reversed_bricks() {
+        let error = Map::get_bricks_for_input("1,2,1~1,0,1\n").unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 7: end coordinate before the start, found '1,0,1'"
+        );
+    }
 }
@@ -1,42 +1,9 @@
-use std::collections::HashSet;
-
-#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
-struct Coord {
-    x: isize,
-    y: isize,
-}
-
-impl Coord {
-    fn get_relative_direction(&self, other: &Coord) -> Direction {
-        if other.y > self.y {
-            return Direction::Down;
-        }
-        if other.y < self.y {
-            return Direction::Up;
-        }
-        if other.x > self.x {
-            return Direction::Right;
-        }
-        if other.x < self.x {
-            return Direction::Left;
-        }
-
-        panic!("No direction found");
-    }
-}
-
-enum PathFindResult {
-    StillSearching,
-    FoundExit(usize),
-}
-
-#[derive(Debug, Clone, Copy, Eq, PartialEq)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
+use crate::error::{ParseError, ParseErrorKind};
+use crate::geometry::{Direction, Point};
+use crate::grid::Grid;
+use crate::search::{bfs, longest_path, Neighbours, Path};
+use crate::solution::{Answer, Solution};
+use std::fmt::{Display, Formatter};
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 enum TileType {
@@ -45,253 +12,447 @@ enum TileType {
     Slope(Direction),
 }
 
-#[derive(Debug, Clone, Copy)]
-struct Tile {
-    tile_type: TileType,
-    position: Coord,
-}
-
 #[derive(Debug, Clone)]
 struct Map {
-    tiles: Vec<Vec<Tile>>,
+    tiles: Grid<TileType>,
 }
 
 impl Map {
-    fn new(input: &str) -> Self {
-        let tiles = input
-            .lines()
-            .enumerate()
-            .map(|(y, line)| {
-                line.chars()
-                    .enumerate()
-                    .map(|(x, c)| {
-                        let tile_type = match c {
-                            '.' => TileType::Path,
-                            '#' => TileType::Forest,
-                            '>' => TileType::Slope(Direction::Right),
-                            '<' => TileType::Slope(Direction::Left),
-                            '^' => TileType::Slope(Direction::Up),
-                            'v' => TileType::Slope(Direction::Down),
-                            _ => panic!("Unknown tile"),
-                        };
-
-                        Tile {
-                            tile_type,
-                            position: Coord {
-                                x: x as isize,
-                                y: y as isize,
-                            },
-                        }
-                    })
-                    .collect::<Vec<Tile>>()
-            })
-            .collect::<Vec<Vec<Tile>>>();
-
-        Self { tiles }
+    fn new(input: &str) -> Result<Self, ParseError> {
+        let tiles = Grid::parse(input, |c| match c {
+            '.' => Ok(TileType::Path),
+            '#' => Ok(TileType::Forest),
+            '>' => Ok(TileType::Slope(Direction::Right)),
+            '<' => Ok(TileType::Slope(Direction::Left)),
+            '^' => Ok(TileType::Slope(Direction::Up)),
+            'v' => Ok(TileType::Slope(Direction::Down)),
+            _ => Err(ParseErrorKind::UnknownToken("tile")),
+        })?;
+
+        Ok(Self { tiles })
     }
 
-    fn get_width(&self) -> usize {
-        self.tiles.get(0).unwrap().len()
-    }
+    fn find_exits(&self) -> Vec<Point> {
+        let (width, height) = (self.tiles.width() as isize, self.tiles.height() as isize);
 
-    fn get_height(&self) -> usize {
-        self.tiles.len()
+        // Checks everything instead of just the edges but whatever
+        self.tiles
+            .iter()
+            .filter(|(point, tile_type)| {
+                (point.y == 0 || point.y == height - 1 || point.x == 0 || point.x == width - 1)
+                    && **tile_type == TileType::Path
+            })
+            .map(|(point, _)| point)
+            .collect()
     }
 
-    fn find_exits(&self) -> Vec<Coord> {
-        // Parses everything instead of just the edges but whatever
+    fn get_walkable_adjacent_coords(&self, coord: &Point, can_climb_slopes: bool) -> Vec<Point> {
         self.tiles
-            .iter()
-            .flatten()
-            .enumerate()
-            .filter(|(index, tile)| {
-                if (tile.position.y == 0
-                    || tile.position.y == self.get_height() as isize - 1
-                    || tile.position.x == 0
-                    || tile.position.x == self.get_width() as isize - 1)
-                    && tile.tile_type == TileType::Path
-                {
-                    true
-                } else {
-                    false
+            .neighbours_4(*coord)
+            .filter(|(possible_coord, tile_type)| match tile_type {
+                TileType::Path => true,
+                TileType::Forest => false,
+                &&TileType::Slope(direction) => {
+                    let step = Direction::between(*coord, *possible_coord);
+
+                    if can_climb_slopes {
+                        return step == Some(direction) || step == Some(direction.reverse());
+                    }
+
+                    step == Some(direction)
                 }
             })
-            .map(|(_, tile)| tile.position)
+            .map(|(possible_coord, _)| possible_coord)
             .collect()
     }
 
-    fn get_tile_at_coord(&self, coord: &Coord) -> &Tile {
-        &self
-            .tiles
-            .get(coord.y as usize)
-            .expect("Tile not found at Y coord")
-            .get(coord.x as usize)
-            .expect("Tile not found at X coord")
+    fn is_fork(&self, coord: &Point) -> bool {
+        self.tiles[(coord.x as usize, coord.y as usize)] != TileType::Forest
+            && self
+                .tiles
+                .neighbours_4(*coord)
+                .filter(|(_, tile_type)| **tile_type != TileType::Forest)
+                .count()
+                > 2
     }
+}
 
-    fn get_walkable_adjacent_coords(
-        &self,
-        coord: &Coord,
-        exclude: &HashSet<Coord>,
-        can_climb_slopes: bool,
-    ) -> Vec<Coord> {
-        let coords = [
-            Coord {
-                x: coord.x - 1,
-                y: coord.y,
-            },
-            Coord {
-                x: coord.x + 1,
-                y: coord.y,
-            },
-            Coord {
-                x: coord.x,
-                y: coord.y - 1,
-            },
-            Coord {
-                x: coord.x,
-                y: coord.y + 1,
-            },
-        ];
-
-        let coords = coords
-            .into_iter()
-            .filter(|coord| {
-                coord.x >= 0
-                    && coord.y >= 0
-                    && coord.x < self.get_width() as isize
-                    && coord.y < self.get_height() as isize
-            })
-            .filter(|possible_coord| !exclude.contains(possible_coord))
-            .filter(|possible_coord| {
-                let tile = self.get_tile_at_coord(&possible_coord);
-
-                match tile.tile_type {
-                    TileType::Path => true,
-                    TileType::Forest => false,
-                    TileType::Slope(direction) => {
-                        if can_climb_slopes {
-                            return direction == coord.get_relative_direction(&possible_coord)
-                                || direction == possible_coord.get_relative_direction(&coord);
-                        }
-
-                        direction == coord.get_relative_direction(&possible_coord)
-                    }
-                }
+/// The map as it looks in the input, with an `O` on every tile of the hike
+struct HikeOnMap<'a> {
+    map: &'a Map,
+    hike: &'a [Point],
+}
+
+impl Display for HikeOnMap<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut tiles = self.map.tiles.map(|tile_type| match tile_type {
+            TileType::Path => '.',
+            TileType::Forest => '#',
+            TileType::Slope(Direction::Right) => '>',
+            TileType::Slope(Direction::Left) => '<',
+            TileType::Slope(Direction::Up) => '^',
+            TileType::Slope(Direction::Down) => 'v',
+        });
+
+        for coord in self.hike {
+            if let Some(tile) = tiles.get_mut(*coord) {
+                *tile = 'O';
+            }
+        }
+
+        write!(f, "{tiles}")
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Corridor {
+    to: usize,
+    /// Every tile after the junction it starts from, up to and including the one it leads to
+    tiles: Vec<Point>,
+}
+
+/// The map with every corridor squashed into a single weighted edge
+///
+/// Only the entrance, the exit and the forks are kept, which leaves a few dozen junctions instead
+/// of thousands of tiles.
+#[derive(Debug, Clone)]
+struct JunctionGraph {
+    junctions: Vec<Point>,
+    /// Per junction, the corridors leading out of it
+    corridors: Vec<Vec<Corridor>>,
+    can_climb_slopes: bool,
+}
+
+impl JunctionGraph {
+    const ENTRANCE: usize = 0;
+    const EXIT: usize = 1;
+
+    fn new(map: &Map, entrance: Point, exit: Point, can_climb_slopes: bool) -> Self {
+        let mut junctions = vec![entrance, exit];
+        junctions.extend(
+            map.tiles
+                .iter()
+                .map(|(coord, _)| coord)
+                .filter(|coord| map.is_fork(coord)),
+        );
+
+        let corridors = junctions
+            .iter()
+            .map(|junction| {
+                map.get_walkable_adjacent_coords(junction, can_climb_slopes)
+                    .into_iter()
+                    .filter_map(|first_step| {
+                        Self::follow_corridor(
+                            map,
+                            &junctions,
+                            *junction,
+                            first_step,
+                            can_climb_slopes,
+                        )
+                    })
+                    .collect()
             })
             .collect();
 
-        coords
+        Self {
+            junctions,
+            corridors,
+            can_climb_slopes,
+        }
     }
 
-    fn find_longest_path(
-        &self,
-        mut start: Coord,
-        end: &Coord,
-        mut traversed: HashSet<Coord>,
-        mut start_length: usize,
+    /// Walks from `junction` through `first_step` to the next junction, `None` for dead ends
+    fn follow_corridor(
+        map: &Map,
+        junctions: &[Point],
+        junction: Point,
+        first_step: Point,
         can_climb_slopes: bool,
-    ) -> PathFindResult {
+    ) -> Option<Corridor> {
+        let (mut previous, mut current) = (junction, first_step);
+        let mut tiles = vec![first_step];
+
         loop {
-            traversed.insert(start.clone());
+            if let Some(to) = junctions.iter().position(|junction| *junction == current) {
+                return Some(Corridor { to, tiles });
+            }
+
+            let next = map
+                .get_walkable_adjacent_coords(&current, can_climb_slopes)
+                .into_iter()
+                .find(|next| *next != previous)?;
+
+            (previous, current) = (current, next);
+            tiles.push(next);
+        }
+    }
+
+    /// Every tile of the longest hike from the entrance to the exit, the cost is its length in steps
+    ///
+    /// Walking down slopes only, the graph has no cycles and the generic search is quick enough.
+    /// Once slopes can be climbed every corridor goes both ways, which needs the bitmask search.
+    fn longest_hike(&self) -> Option<Path<Point>> {
+        let route = if self.can_climb_slopes {
+            let mut longest = None;
+            self.explore(
+                Self::ENTRANCE,
+                1 << Self::ENTRANCE,
+                0,
+                &mut vec![Self::ENTRANCE],
+                &mut longest,
+            );
+
+            longest?.1
+        } else {
+            longest_path(self, Self::ENTRANCE, |junction| *junction == Self::EXIT)?.nodes
+        };
+
+        let mut nodes = vec![self.junctions[Self::ENTRANCE]];
+        let mut cost = 0;
+
+        for pair in route.windows(2) {
+            let corridor = self.longest_corridor(pair[0], pair[1])?;
+
+            nodes.extend(&corridor.tiles);
+            cost += corridor.tiles.len();
+        }
+
+        Some(Path { cost, nodes })
+    }
+
+    /// Two forks can be linked by more than one corridor, a longest hike takes the longest
+    fn longest_corridor(&self, from: usize, to: usize) -> Option<&Corridor> {
+        self.corridors[from]
+            .iter()
+            .filter(|corridor| corridor.to == to)
+            .max_by_key(|corridor| corridor.tiles.len())
+    }
 
-            if start == *end {
-                return PathFindResult::FoundExit(start_length);
+    /// `visited` has a bit set for every junction already on `route`
+    fn explore(
+        &self,
+        junction: usize,
+        visited: u64,
+        length: usize,
+        route: &mut Vec<usize>,
+        longest: &mut Option<(usize, Vec<usize>)>,
+    ) {
+        if junction == Self::EXIT {
+            if longest
+                .as_ref()
+                .is_none_or(|(longest, _)| length > *longest)
+            {
+                *longest = Some((length, route.clone()));
             }
+            return;
+        }
 
-            let possible_coords =
-                self.get_walkable_adjacent_coords(&start, &traversed, can_climb_slopes);
-
-            if possible_coords.len() == 1 {
-                start = *possible_coords.get(0).unwrap();
-                start_length += 1;
-            } else {
-                return if let Some(longest_path) = possible_coords
-                    .iter()
-                    .map(|coord| {
-                        self.find_longest_path(
-                            coord.clone(),
-                            &end,
-                            traversed.clone(),
-                            start_length + 1,
-                            can_climb_slopes,
-                        )
-                    })
-                    .filter(|result| match result {
-                        PathFindResult::StillSearching => false,
-                        PathFindResult::FoundExit(_) => true,
-                    })
-                    .map(|result| match result {
-                        PathFindResult::StillSearching => panic!("Shouldn't happen"),
-                        PathFindResult::FoundExit(length) => length,
-                    })
-                    .max()
-                {
-                    println!("Found exit with {} steps", longest_path);
-                    PathFindResult::FoundExit(longest_path)
-                } else {
-                    PathFindResult::StillSearching
-                };
+        for corridor in &self.corridors[junction] {
+            if visited & 1 << corridor.to != 0 {
+                continue;
+            }
+
+            route.push(corridor.to);
+            self.explore(
+                corridor.to,
+                visited | 1 << corridor.to,
+                length + corridor.tiles.len(),
+                route,
+                longest,
+            );
+            route.pop();
+        }
+    }
+}
+
+/// Corridors are the edges, weighted by their length in steps
+impl Neighbours<usize> for JunctionGraph {
+    fn neighbours(&self, junction: &usize) -> Vec<(usize, usize)> {
+        self.corridors[*junction]
+            .iter()
+            .map(|corridor| (corridor.to, corridor.tiles.len()))
+            .collect()
+    }
+}
+
+/// One corridor per line, e.g. `1,0 -> 3,5: 15`
+impl Display for JunctionGraph {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (from, corridors) in self.junctions.iter().zip(&self.corridors) {
+            for corridor in corridors {
+                let to = self.junctions[corridor.to];
+                let length = corridor.tiles.len();
+                writeln!(f, "{},{} -> {},{}: {length}", from.x, from.y, to.x, to.y)?;
             }
         }
+
+        Ok(())
     }
 }
 
-fn calculate(input: &str) -> (usize, usize) {
-    let map = Map::new(input);
+/// The character of `input` the tile at `point` was read from, for pointing errors at it
+fn tile_text(input: &str, point: Point) -> &str {
+    input
+        .lines()
+        .filter(|line| !line.is_empty())
+        .nth(point.y as usize)
+        .and_then(|row| row.get(point.x as usize..point.x as usize + 1))
+        .unwrap_or(&input[input.len()..])
+}
+
+fn junction_graph(input: &str, can_climb_slopes: bool) -> Result<(Map, JunctionGraph), ParseError> {
+    let map = Map::new(input)?;
 
     let exits = map.find_exits();
 
-    let longest_path_size = match map.find_longest_path(
-        exits.get(0).unwrap().clone(),
-        exits.get(1).unwrap(),
-        HashSet::new(),
-        0,
-        false,
-    ) {
-        PathFindResult::StillSearching => panic!("Exit not found!"),
-        PathFindResult::FoundExit(length) => length,
-    };
+    if exits.len() != 2 {
+        // The entrance should be on the first row and the exit on the last, whichever doesn't
+        // have exactly one is reported
+        let mut rows = input.lines().filter(|line| !line.is_empty());
+        let first_row = rows.next().unwrap_or(&input[input.len()..]);
+        let row = match exits.iter().filter(|exit| exit.y == 0).count() {
+            1 => rows.next_back().unwrap_or(first_row),
+            _ => first_row,
+        };
+
+        return Err(ParseError::new(
+            input,
+            row,
+            ParseErrorKind::Invalid("expected exactly one entrance and one exit"),
+        ));
+    }
 
-    let longest_path_size_without_slopes = match map.find_longest_path(
-        exits.get(0).unwrap().clone(),
-        exits.get(1).unwrap(),
-        HashSet::new(),
-        0,
-        true,
-    ) {
-        PathFindResult::StillSearching => panic!("Exit not found"),
-        PathFindResult::FoundExit(length) => length,
+    let graph = JunctionGraph::new(&map, exits[0], exits[1], can_climb_slopes);
+
+    if graph.junctions.len() > u64::BITS as usize {
+        return Err(ParseError::new(
+            input,
+            tile_text(input, graph.junctions[u64::BITS as usize]),
+            ParseErrorKind::Invalid("more than 64 forks in the trails"),
+        ));
+    }
+
+    Ok((map, graph))
+}
+
+fn find_longest_hike(
+    input: &str,
+    can_climb_slopes: bool,
+) -> Result<(Map, Path<Point>), ParseError> {
+    let (map, graph) = junction_graph(input, can_climb_slopes)?;
+    let unreachable = || {
+        ParseError::new(
+            input,
+            tile_text(input, graph.junctions[JunctionGraph::EXIT]),
+            ParseErrorKind::Invalid("the exit can't be reached"),
+        )
     };
 
-    (longest_path_size, longest_path_size_without_slopes)
+    // Finding out there's no way through is much cheaper than trying every hike
+    bfs(&graph, JunctionGraph::ENTRANCE, |junction| {
+        *junction == JunctionGraph::EXIT
+    })
+    .ok_or_else(unreachable)?;
+
+    let hike = graph.longest_hike().ok_or_else(unreachable)?;
+
+    Ok((map, hike))
+}
+
+/// The corridors between the forks of the map, one per line
+pub fn render_junctions(input: &str, can_climb_slopes: bool) -> Result<String, ParseError> {
+    Ok(junction_graph(input, can_climb_slopes)?.1.to_string())
+}
+
+pub fn calculate(input: &str, can_climb_slopes: bool) -> Result<usize, ParseError> {
+    Ok(find_longest_hike(input, can_climb_slopes)?.1.cost)
+}
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    fn year(&self) -> u16 {
+        2023
+    }
+
+    fn day(&self) -> u8 {
+        23
+    }
+
+    fn title(&self) -> &'static str {
+        "A Long Walk"
+    }
+
+    fn fixtures(&self) -> &'static [(&'static str, &'static str)] {
+        &[("example", include_str!("test_input.txt"))]
+    }
+
+    fn parse(&self, input: &str) -> Option<Result<(), ParseError>> {
+        Some(Map::new(input).map(drop))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, ParseError> {
+        Ok(calculate(input, false)?.into())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, ParseError> {
+        Ok(calculate(input, true)?.into())
+    }
+
+    fn render(&self, part: u8, input: &str) -> Option<Result<String, ParseError>> {
+        let can_climb_slopes = match part {
+            1 => false,
+            2 => true,
+            _ => return None,
+        };
+
+        let render = || {
+            let (map, hike) = find_longest_hike(input, can_climb_slopes)?;
+            let hike = HikeOnMap {
+                map: &map,
+                hike: &hike.nodes,
+            };
+
+            Ok(format!(
+                "{hike}\n{}",
+                render_junctions(input, can_climb_slopes)?
+            ))
+        };
+
+        Some(render())
+    }
 }
 
 #[cfg(test)]
 mod day1_tests {
-    use crate::questions_2023::day23::{calculate, Coord, Map};
+    use crate::geometry::Direction;
+    use crate::input::optional_puzzle_input;
+    use crate::questions_2023::day23::{
+        calculate, render_junctions, HikeOnMap, JunctionGraph, Map, Point, TileType,
+    };
     use std::collections::HashSet;
 
     #[test]
     fn test_input_challenge() {
         let input = include_str!("test_input.txt");
-        let result = calculate(input);
-
-        assert_eq!(result, (94, 154));
+        assert_eq!(calculate(input, false), Ok(94));
+        assert_eq!(calculate(input, true), Ok(154));
     }
 
     #[test]
     fn input_challenge() {
-        let input = include_str!("input.txt");
-        let result = calculate(input);
+        let Some(input) = &optional_puzzle_input(2023, 23) else {
+            return;
+        };
+        let result = (calculate(input, false), calculate(input, true));
 
-        println!("Challenge 0: {}, challenge 1: {}", result.0, result.1)
+        println!("Challenge 0: {:?}, challenge 1: {:?}", result.0, result.1)
     }
 
     #[test]
     fn find_exits() {
         let input = include_str!("test_input.txt");
-        let map = Map::new(input);
+        let map = Map::new(input).unwrap();
 
         let exits = map.find_exits();
 
@@ -301,41 +462,132 @@ mod day1_tests {
     #[test]
     fn get_adjacent() {
         let input = include_str!("test_input.txt");
-        let map = Map::new(input);
-        let mut traversed = HashSet::new();
+        let map = Map::new(input).unwrap();
 
         assert_eq!(
-            map.get_walkable_adjacent_coords(&Coord { x: 1, y: 0 }, &traversed, false)
+            map.get_walkable_adjacent_coords(&Point::new(1, 0), false)
                 .len(),
             1
         );
         assert_eq!(
-            map.get_walkable_adjacent_coords(&Coord { x: 2, y: 1 }, &traversed, false)
+            map.get_walkable_adjacent_coords(&Point::new(2, 1), false)
                 .len(),
             2
         );
 
         assert_eq!(
-            map.get_walkable_adjacent_coords(&Coord { x: 2, y: 1 }, &traversed, false)
+            map.get_walkable_adjacent_coords(&Point::new(11, 3), false)
                 .len(),
             2
         );
-
         assert_eq!(
-            map.get_walkable_adjacent_coords(&Coord { x: 11, y: 3 }, &traversed, false)
+            map.get_walkable_adjacent_coords(&Point::new(13, 3), false)
                 .len(),
-            2
+            1
         );
         assert_eq!(
-            map.get_walkable_adjacent_coords(&Coord { x: 13, y: 3 }, &traversed, false)
+            map.get_walkable_adjacent_coords(&Point::new(13, 3), true)
                 .len(),
-            1
+            2
         );
+    }
 
-        let vec = map.get_walkable_adjacent_coords(&Coord { x: 11, y: 3 }, &traversed, false);
-        traversed.insert(vec.get(0).unwrap().clone());
-        let vec2 = map.get_walkable_adjacent_coords(&Coord { x: 11, y: 3 }, &traversed, false);
+    #[test]
+    fn contracts_corridors() {
+        let input = include_str!("test_input.txt");
+        let map = Map::new(input).unwrap();
+        let exits = map.find_exits();
+
+        let graph = JunctionGraph::new(&map, exits[0], exits[1], false);
+
+        assert_eq!(graph.junctions.len(), 9);
+        assert_eq!(graph.to_string().lines().next(), Some("1,0 -> 3,5: 15"));
+        assert_eq!(render_junctions(input, false), Ok(graph.to_string()));
+        assert_eq!(graph.longest_hike().unwrap().cost, 94);
+
+        let graph = JunctionGraph::new(&map, exits[0], exits[1], true);
+        let corridors = graph.to_string().lines().count();
+
+        // Every corridor can be walked both ways once slopes can be climbed
+        assert_eq!(corridors % 2, 0);
+        assert_eq!(graph.longest_hike().unwrap().cost, 154);
+    }
+
+    #[test]
+    fn hike_only_walks_down_slopes() {
+        let input = include_str!("test_input.txt");
+        let map = Map::new(input).unwrap();
+        let exits = map.find_exits();
+
+        let hike = JunctionGraph::new(&map, exits[0], exits[1], false)
+            .longest_hike()
+            .unwrap();
+
+        assert_eq!(hike.nodes.len(), 95);
+        assert_eq!(hike.nodes.iter().collect::<HashSet<_>>().len(), 95);
+
+        for step in hike.nodes.windows(2) {
+            let direction = Direction::between(step[0], step[1]).unwrap();
+
+            if let Some(&TileType::Slope(slope)) = map.tiles.get(step[1]) {
+                assert_eq!(direction, slope, "climbed the slope at {:?}", step[1]);
+            }
+        }
+
+        let rendered = HikeOnMap {
+            map: &map,
+            hike: &hike.nodes,
+        }
+        .to_string();
+
+        assert_eq!(rendered.matches('O').count(), 95);
+        assert!(rendered.starts_with("#O#####################\n#OOOOOOO#########...#"));
+    }
+
+    #[test]
+    fn reports_unknown_tile() {
+        let error = Map::new("#.###\n#..x#\n###.#\n").unwrap_err();
 
-        assert_eq!(vec.len() - 1, vec2.len());
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 4: unknown tile, found 'x'"
+        );
+    }
+
+    #[test]
+    fn reports_missing_and_extra_exits() {
+        let error = calculate("#.#.#\n#...#\n###.#\n", false).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 1: expected exactly one entrance and one exit, found '#.#.#'"
+        );
+
+        let error = calculate("#.###\n#...#\n#####\n", false).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 3, column 1: expected exactly one entrance and one exit, found '#####'"
+        );
+    }
+
+    #[test]
+    fn reports_too_many_forks() {
+        let open = "#..........#\n".repeat(10);
+        let input = format!("#.##########\n{open}##########.#\n");
+
+        let error = calculate(&input, false).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 8, column 5: more than 64 forks in the trails, found '.'"
+        );
+    }
+
+    #[test]
+    fn reports_unreachable_exit() {
+        let error = calculate("#.###\n#.#.#\n###.#\n", true).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "line 3, column 4: the exit can't be reached, found '.'"
+        );
     }
 }
@@ -1,117 +1,143 @@
+use crate::error::{parse_integer, ParseError};
+use crate::geometry::Point;
+use crate::grid::Grid;
+use crate::solution::{Answer, Solution};
 use std::collections::HashMap;
 
-fn is_adjacent(coord: (usize, usize), input: &Vec<Vec<char>>) -> (bool, Option<(usize, usize)>) {
-    let coord = (coord.0 as isize, coord.1 as isize);
-    let adjacent_coords = vec![
-        (coord.0 - 1, coord.1 - 1),
-        (coord.0 - 1, coord.1),
-        (coord.0 - 1, coord.1 + 1),
-        (coord.0, coord.1 - 1),
-        (coord.0, coord.1 + 1),
-        (coord.0 + 1, coord.1 - 1),
-        (coord.0 + 1, coord.1),
-        (coord.0 + 1, coord.1 + 1),
-    ];
-
-    let mut gear_coord: Option<(usize, usize)> = None;
-
-    let result = adjacent_coords.iter().any(|&(x, y)| {
-        if let Some(Some(character)) = input.get(y as usize).map(|row| row.get(x as usize)) {
-            if !character.is_numeric() && *character != '.' {
-                if *character == '*' {
-                    gear_coord = Some((x as usize, y as usize));
-                }
-
-                return true;
+struct PartNumber {
+    value: usize,
+    cells: Vec<Point>,
+    /// Numbers ending a row never counted towards a gear
+    ends_row: bool,
+}
+
+fn is_symbol(character: char) -> bool {
+    !character.is_ascii_digit() && character != '.'
+}
+
+fn parse_schematic(input: &str) -> Result<(Grid<char>, Vec<PartNumber>), ParseError> {
+    let grid = Grid::parse(input, Ok)?;
+    let mut numbers = Vec::new();
+
+    for (y, line) in input.lines().filter(|line| !line.is_empty()).enumerate() {
+        let mut number_start: Option<(usize, usize)> = None;
+
+        let characters = line.char_indices().map(Some).chain([None]);
+
+        for (x, character) in characters.enumerate() {
+            if let Some((index, _)) = character.filter(|(_, c)| c.is_ascii_digit()) {
+                number_start.get_or_insert((x, index));
+                continue;
             }
-        }
 
-        false
-    });
+            if let Some((start_x, start)) = number_start.take() {
+                let end = character.map_or(line.len(), |(index, _)| index);
+
+                numbers.push(PartNumber {
+                    value: parse_integer(input, &line[start..end])?,
+                    cells: (start_x..x)
+                        .map(|x| Point::new(x as isize, y as isize))
+                        .collect(),
+                    ends_row: character.is_none(),
+                });
+            }
+        }
+    }
 
-    (result, gear_coord)
+    Ok((grid, numbers))
 }
 
-fn calculate(input: &str) -> (usize, usize) {
-    let input = input
-        .split("\n")
-        .collect::<Vec<&str>>()
-        .into_iter()
-        .map(|x| x.chars().collect::<Vec<char>>())
-        .collect::<Vec<Vec<char>>>();
+pub fn calculate(input: &str) -> Result<(usize, usize), ParseError> {
+    let (grid, numbers) = parse_schematic(input)?;
 
     let mut sum = 0;
-    let mut gears: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
-
-    input.iter().enumerate().for_each(|(y, row)| {
-        let mut number = String::new();
-        let mut number_is_adjecent = false;
-        let mut gear_coord: Option<(usize, usize)> = None;
-
-        row.iter().enumerate().for_each(|(x, character)| {
-            if character.is_numeric() {
-                number.push(*character);
-            } else {
-                if number.len() > 0 {
-                    if number_is_adjecent {
-                        let total = number.parse::<usize>().unwrap();
-                        gear_coord.map(|coord| {
-                            gears.entry(coord).or_insert(Vec::new()).push(total);
-                        });
-
-                        sum += total;
-                        println!("{} is adjacent", number);
-                    }
-                    number_is_adjecent = false;
-                    number.clear();
-                    gear_coord = None;
-                }
-            }
+    let mut gears: HashMap<Point, Vec<usize>> = HashMap::new();
 
-            if !number_is_adjecent && number.len() > 0 {
-                let (is_adjacent, found_gear_coord) = is_adjacent((x, y), &input);
+    for number in &numbers {
+        let symbol = number
+            .cells
+            .iter()
+            .flat_map(|cell| grid.neighbours_8(*cell))
+            .find(|(_, character)| is_symbol(**character));
 
-                if is_adjacent {
-                    number_is_adjecent = true;
-                }
-                if found_gear_coord.is_some() {
-                    gear_coord = found_gear_coord;
-                }
-            }
-        });
+        let Some((point, character)) = symbol else {
+            continue;
+        };
 
-        if number_is_adjecent {
-            sum += number.parse::<usize>().unwrap();
-            println!("{} is adjacent", number);
+        if *character == '*' && !number.ends_row {
+            gears.entry(point).or_default().push(number.value);
         }
-    });
+
+        sum += number.value;
+    }
 
     let gear_total = gears
-        .iter()
-        .filter(|(_, numbers)| numbers.len() == 2)
-        .map(|(_, numbers)| numbers.get(0).unwrap() * numbers.get(1).unwrap())
-        .reduce(|a, b| a + b)
-        .unwrap();
+        .values()
+        .filter(|numbers| numbers.len() == 2)
+        .map(|numbers| numbers[0] * numbers[1])
+        .sum();
+
+    Ok((sum, gear_total))
+}
 
-    (sum, gear_total)
+pub struct Day3;
+
+impl Solution for Day3 {
+    fn year(&self) -> u16 {
+        2023
+    }
+
+    fn day(&self) -> u8 {
+        3
+    }
+
+    fn title(&self) -> &'static str {
+        "Gear Ratios"
+    }
+
+    fn fixtures(&self) -> &'static [(&'static str, &'static str)] {
+        &[("example", include_str!("test_input.txt"))]
+    }
+
+    fn parse(&self, input: &str) -> Option<Result<(), ParseError>> {
+        Some(parse_schematic(input).map(drop))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, ParseError> {
+        Ok(calculate(input)?.0.into())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, ParseError> {
+        Ok(calculate(input)?.1.into())
+    }
 }
 
 #[cfg(test)]
 mod day1_tests {
+    use crate::input::puzzle_input;
     use crate::questions_2023::day3::calculate;
 
     #[test]
     fn test_input_challenge_1() {
         let input = include_str!("test_input.txt");
-        let result = calculate(input);
+        let result = calculate(input).unwrap();
 
         assert_eq!(result, (4361, 467835));
     }
 
+    #[test]
+    fn reports_number_too_large() {
+        let input = "99999999999999999999999*\n";
+        let error = calculate(input).unwrap_err();
+
+        assert_eq!((error.line, error.column), (1, 1));
+        assert_eq!(error.found, "99999999999999999999999");
+    }
+
     #[test]
     fn input_challenge_1() {
-        let input = include_str!("input.txt");
-        let result = calculate(input);
+        let input = &puzzle_input(2023, 3);
+        let result = calculate(input).unwrap();
 
         println!("{:?}", result)
     }
@@ -1,3 +1,5 @@
+use crate::error::{expect_field, parse_integer, ParseError};
+use crate::solution::{Answer, Solution};
 use std::collections::HashMap;
 
 #[derive(Debug, Clone)]
@@ -8,11 +10,12 @@ struct Card {
     points: usize,
     correct_numbers: usize,
 }
-fn calculate(input: &str) -> (usize, usize) {
-    let mut cards: Vec<Card> = input
+
+fn parse_cards(input: &str) -> Result<Vec<Card>, ParseError> {
+    input
         .split("\n")
         .filter(|line| !line.is_empty())
-        .map(|line| {
+        .map(|line| -> Result<Card, ParseError> {
             let mut card = Card {
                 card_number: 0,
                 winning_numbers: Vec::new(),
@@ -23,32 +26,37 @@ fn calculate(input: &str) -> (usize, usize) {
 
             let mut splitted_line = line.split(": ");
 
-            card.card_number = splitted_line
-                .next()
-                .unwrap()
-                .replace("Card", "")
-                .trim()
-                .parse::<usize>()
-                .unwrap();
+            card.card_number = parse_integer(
+                input,
+                expect_field(input, line, splitted_line.next(), "card number")?
+                    .trim_start_matches("Card")
+                    .trim(),
+            )?;
 
-            let mut splitted_numbers = splitted_line.next().unwrap().split("|");
+            let numbers = expect_field(input, line, splitted_line.next(), "numbers")?;
+            let mut splitted_numbers = numbers.split("|");
 
-            card.winning_numbers = splitted_numbers
-                .next()
-                .unwrap()
-                .split(" ")
-                .filter_map(|x| x.parse::<usize>().ok())
-                .collect::<Vec<usize>>();
+            card.winning_numbers =
+                expect_field(input, line, splitted_numbers.next(), "winning numbers")?
+                    .split(" ")
+                    .filter(|x| !x.is_empty())
+                    .map(|x| parse_integer(input, x))
+                    .collect::<Result<Vec<usize>, ParseError>>()?;
 
-            card.your_numbers = splitted_numbers
-                .next()
-                .unwrap()
+            card.your_numbers = expect_field(input, line, splitted_numbers.next(), "your numbers")?
                 .split(" ")
-                .filter_map(|x| x.parse::<usize>().ok())
-                .collect::<Vec<usize>>();
+                .filter(|x| !x.is_empty())
+                .map(|x| parse_integer(input, x))
+                .collect::<Result<Vec<usize>, ParseError>>()?;
 
-            card
+            Ok(card)
         })
+        .collect()
+}
+
+pub fn calculate(input: &str) -> Result<(usize, usize), ParseError> {
+    let cards: Vec<Card> = parse_cards(input)?
+        .into_iter()
         .map(|card| {
             let correct_numbers = card
                 .your_numbers
@@ -76,12 +84,13 @@ fn calculate(input: &str) -> (usize, usize) {
     cards.iter().enumerate().for_each(|(index, card)| {
         let correct_numbers = card.correct_numbers;
 
-        for x in 0..*amounts.get(index).unwrap() {
-            for i in 1..correct_numbers + 1 {
-                let card_index = index + i;
+        let copies = amounts[index];
 
-                *amounts.get_mut(card_index).unwrap() += 1;
-            }
+        // Cards never make copies of cards past the end of the table
+        let won = index + 1..(index + 1 + correct_numbers).min(amounts.len());
+
+        for card_index in won {
+            amounts[card_index] += copies;
         }
     });
 
@@ -89,25 +98,65 @@ fn calculate(input: &str) -> (usize, usize) {
 
     let total_points = cards.iter().fold(0, |acc, card| acc + card.points);
 
-    (total_points, total_cards)
+    Ok((total_points, total_cards))
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    fn year(&self) -> u16 {
+        2023
+    }
+
+    fn day(&self) -> u8 {
+        4
+    }
+
+    fn title(&self) -> &'static str {
+        "Scratchcards"
+    }
+
+    fn fixtures(&self) -> &'static [(&'static str, &'static str)] {
+        &[("example", include_str!("test_input.txt"))]
+    }
+
+    fn parse(&self, input: &str) -> Option<Result<(), ParseError>> {
+        Some(parse_cards(input).map(drop))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, ParseError> {
+        Ok(calculate(input)?.0.into())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, ParseError> {
+        Ok(calculate(input)?.1.into())
+    }
 }
 
 #[cfg(test)]
 mod day1_tests {
+    use crate::input::puzzle_input;
     use crate::questions_2023::day4::calculate;
 
     #[test]
     fn test_input_challenge() {
         let input = include_str!("test_input.txt");
-        let result = calculate(input);
+        let result = calculate(input).unwrap();
 
         assert_eq!(result, (13, 30));
     }
 
+    #[test]
+    fn ignores_copies_past_the_last_card() {
+        let result = calculate("Card 1: 1 2 | 1 2\n").unwrap();
+
+        assert_eq!(result, (2, 1));
+    }
+
     #[test]
     fn input_challenge() {
-        let input = include_str!("input.txt");
-        let result = calculate(input);
+        let input = &puzzle_input(2023, 4);
+        let result = calculate(input).unwrap();
 
         println!("Challenge 0: {}, challenge 1: {}", result.0, result.1)
     }
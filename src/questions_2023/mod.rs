@@ -1,7 +1,7 @@
 pub mod day1;
 pub mod day2;
 pub mod day3;
-mod day4;
+pub mod day4;
 
-mod day22;
-mod day23;
+pub mod day22;
+pub mod day23;
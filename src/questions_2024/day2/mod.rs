@@ -1,32 +1,52 @@
+use crate::error::{parse_integer, ParseError};
 use crate::questions_2024::day2::Following::{Decreasing, Increasing};
+use crate::solution::{Answer, Solution};
+
+fn parse_reports(input: &str) -> Result<Vec<Vec<u32>>, ParseError> {
+    input
+        .split("\n")
+        .filter(|report| !report.is_empty())
+        .map(|report| {
+            report
+                .split(" ")
+                .map(|chr| parse_integer::<u32>(input, chr))
+                .collect()
+        })
+        .collect()
+}
 
-fn calculate(input: &str, enable_dampener: bool) -> usize {
-    let reports = input.split("\n").collect::<Vec<&str>>();
-
-    let safe_reports: Vec<&str> = reports.into_iter().filter(|report| {
-        let report: Vec<u32> = report.split(" ").map(|chr| chr.parse::<u32>().unwrap()).collect();
-
-        let is_correct = record_is_correct(&report);
+pub fn calculate(input: &str, enable_dampener: bool) -> Result<usize, ParseError> {
+    let reports = parse_reports(input)?;
 
-        if is_correct {
-            return true;
-        }
+    let safe_reports: Vec<Vec<u32>> = reports
+        .into_iter()
+        .filter(|report| {
+            let is_correct = record_is_correct(&report);
 
-        if enable_dampener {
-            for i in 0..report.len() {
-                let dampened_report: Vec<u32> = report.iter().enumerate().filter(|(index, _)| *index != i).map(|(_, val) | *val).collect();
+            if is_correct {
+                return true;
+            }
 
-                if record_is_correct(&dampened_report) {
-                    return true;
+            if enable_dampener {
+                for i in 0..report.len() {
+                    let dampened_report: Vec<u32> = report
+                        .iter()
+                        .enumerate()
+                        .filter(|(index, _)| *index != i)
+                        .map(|(_, val)| *val)
+                        .collect();
+
+                    if record_is_correct(&dampened_report) {
+                        return true;
+                    }
                 }
             }
-        }
 
-        false
+            false
+        })
+        .collect();
 
-    }).collect();
-
-    safe_reports.len()
+    Ok(safe_reports.len())
 }
 
 #[derive(Eq, PartialEq)]
@@ -36,20 +56,24 @@ enum Following {
 }
 
 fn record_is_correct(record: &[u32]) -> bool {
-    let first = *record.get(0).unwrap();
-    let second = *record.get(1).unwrap();
+    if record.len() < 2 {
+        return true;
+    }
+
+    let first = record[0];
+    let second = record[1];
 
     let following = if first > second {
         Decreasing
     } else if first < second {
         Increasing
     } else {
-        return false
+        return false;
     };
 
     for window in record.windows(2) {
-        let first = *window.get(0).unwrap();
-        let second = *window.get(1).unwrap();
+        let first = window[0];
+        let second = window[1];
 
         let delta = first.abs_diff(second);
 
@@ -74,44 +98,83 @@ fn record_is_correct(record: &[u32]) -> bool {
     true
 }
 
+pub struct Day2;
+
+impl Solution for Day2 {
+    fn year(&self) -> u16 {
+        2024
+    }
+
+    fn day(&self) -> u8 {
+        2
+    }
+
+    fn title(&self) -> &'static str {
+        "Red-Nosed Reports"
+    }
+
+    fn fixtures(&self) -> &'static [(&'static str, &'static str)] {
+        &[("example", include_str!("test_input.txt"))]
+    }
+
+    fn parse(&self, input: &str) -> Option<Result<(), ParseError>> {
+        Some(parse_reports(input).map(drop))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, ParseError> {
+        Ok(calculate(input, false)?.into())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, ParseError> {
+        Ok(calculate(input, true)?.into())
+    }
+}
+
 #[cfg(test)]
 mod day2_tests {
+    use crate::input::puzzle_input;
     use crate::questions_2024::day2::calculate;
 
     #[test]
     fn test_input_challenge_1() {
         let input = include_str!("test_input.txt");
-        let result = calculate(input, false);
+        let result = calculate(input, false).unwrap();
 
         assert_eq!(result, 2);
     }
 
     #[test]
     fn test_challenge_1() {
-        let input = include_str!("input.txt");
-
-        // Calculate the time for the following call
-        let current_time = std::time::Instant::now();
-        let result = calculate(input, false);
+        let input = &puzzle_input(2024, 2);
 
-        println!("Time: {:?}", current_time.elapsed());
+        let result = calculate(input, false).unwrap();
 
-        println!("{}", result);
+        assert_eq!(result, 502);
     }
 
     #[test]
     fn test_input_challenge_2() {
         let input = include_str!("test_input.txt");
-        let result = calculate(input, true);
+        let result = calculate(input, true).unwrap();
 
         assert_eq!(result, 4);
     }
 
     #[test]
     fn test_challenge_2() {
-        let input = include_str!("input.txt");
-        let result = calculate(input, true);
+        let input = &puzzle_input(2024, 2);
+        let result = calculate(input, true).unwrap();
 
         println!("{}", result);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn reports_malformed_level() {
+        let error = calculate("7 6 4 2 1\n1 2  8 9\n", false).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 5: expected integer, found ''"
+        );
+    }
+}
@@ -1,52 +1,107 @@
+use crate::error::ParseError;
+use crate::solution::{Answer, Solution};
 use regex::Regex;
 
-fn calculate(input: &str) -> usize {
-    let mut input = format!("do(){input}");
+enum Instruction {
+    Mul(usize, usize),
+    Do,
+    Dont,
+}
+
+fn parse_instructions(input: &str) -> Vec<Instruction> {
+    let instruction_regex =
+        Regex::new("mul\\(([0-9]{1,3}),([0-9]{1,3})\\)|do\\(\\)|don't\\(\\)").unwrap();
+
+    instruction_regex
+        .captures_iter(input)
+        .map(|captures| match &captures[0] {
+            "do()" => Instruction::Do,
+            "don't()" => Instruction::Dont,
+            // At most three digits, they always fit
+            _ => Instruction::Mul(captures[1].parse().unwrap(), captures[2].parse().unwrap()),
+        })
+        .collect()
+}
+
+pub fn calculate(input: &str, handle_conditionals: bool) -> usize {
+    let mut enabled = true;
 
-    loop {
-        if let Some(dont_index) = input.find("don't()") {
-            let do_index = input[dont_index..].find("do()").unwrap_or(input[dont_index..].len());
+    parse_instructions(input)
+        .into_iter()
+        .filter_map(|instruction| match instruction {
+            Instruction::Mul(first, second) => enabled.then_some(first * second),
+            Instruction::Do => {
+                enabled = true;
+                None
+            }
+            Instruction::Dont => {
+                enabled = !handle_conditionals;
+                None
+            }
+        })
+        .sum()
+}
+
+pub struct Day3;
 
-            input.replace_range(&dont_index..&(do_index+dont_index), &"");
+impl Solution for Day3 {
+    fn year(&self) -> u16 {
+        2024
+    }
+
+    fn day(&self) -> u8 {
+        3
+    }
 
-            println!("replaced")
-        } else {
-            println!("Done");
-            break;
-        }
+    fn title(&self) -> &'static str {
+        "Mull It Over"
     }
 
+    fn fixtures(&self) -> &'static [(&'static str, &'static str)] {
+        &[("example", include_str!("test_input.txt"))]
+    }
 
-    let mul_regex = Regex::new("mul\\([0-9]{1,3}\\,[0-9]{1,3}\\)").unwrap();
+    fn parse(&self, input: &str) -> Option<Result<(), ParseError>> {
+        parse_instructions(input);
 
-    mul_regex.find_iter(&input).map(|matches| {
-        let operation = matches.as_str();
+        Some(Ok(()))
+    }
 
-        let parts: Vec<&str> = operation.split(",").collect();
-        let first = parts.get(0).unwrap().replace("mul(", "").parse::<usize>().unwrap();
-        let second = parts.get(1).unwrap().replace(")", "").parse::<usize>().unwrap();
+    fn part1(&self, input: &str) -> Result<Answer, ParseError> {
+        Ok(calculate(input, false).into())
+    }
 
-        first * second
-    }).sum()
+    fn part2(&self, input: &str) -> Result<Answer, ParseError> {
+        Ok(calculate(input, true).into())
+    }
 }
 
 #[cfg(test)]
 mod day3_tests {
+    use crate::input::puzzle_input;
     use crate::questions_2024::day3::calculate;
 
     #[test]
     fn test_input_challenge_1() {
         let input = include_str!("test_input.txt");
-        let result = calculate(input);
+        let result = calculate(input, false);
+
+        assert_eq!(result, 161);
+    }
+
+    #[test]
+    fn test_input_challenge_2() {
+        let input = include_str!("test_input.txt");
+        let result = calculate(input, true);
 
         assert_eq!(result, 48);
     }
 
     #[test]
-    fn input_challenge_1() {
-        let input = include_str!("input.txt");
-        let result = calculate(input);
+    fn input_challenge_2() {
+        let input = &puzzle_input(2024, 3);
+        let result = calculate(input, true);
 
         println!("{result}")
     }
-}
\ No newline at end of file
+}
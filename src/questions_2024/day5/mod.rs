@@ -1,41 +1,63 @@
-fn calculate(input: &str, correct: bool) -> usize {
+use crate::error::{expect_field, parse_integer, ParseError};
+use crate::solution::{Answer, Solution};
+
+/// Pairs of pages where the first has to be printed before the second
+type Rules = Vec<(u32, u32)>;
+
+fn parse_input(input: &str) -> Result<(Rules, Vec<Vec<u32>>), ParseError> {
     let sections: Vec<_> = input.split("\n\n").collect();
 
-    let ordering: Vec<(u32, u32)> = sections
-        .get(0)
-        .unwrap()
+    let ordering: Vec<(u32, u32)> = expect_field(input, input, sections.first().copied(), "rules")?
         .split("\n")
         .filter(|line| !line.is_empty())
         .map(|line| {
-            let nums: Vec<u32> = line.split("|").map(|part| part.parse::<u32>().unwrap()).collect();
-
-            (*nums.get(0).unwrap(), *nums.get(1).unwrap())
+            let mut nums = line.split("|");
+
+            Ok((
+                parse_integer(input, expect_field(input, line, nums.next(), "first page")?)?,
+                parse_integer(
+                    input,
+                    expect_field(input, line, nums.next(), "second page")?,
+                )?,
+            ))
         })
-        .collect();
+        .collect::<Result<_, ParseError>>()?;
 
-    let docs: Vec<Vec<u32>> = sections
-        .get(1)
-        .unwrap()
+    let docs: Vec<Vec<u32>> = expect_field(input, input, sections.get(1).copied(), "updates")?
         .split("\n")
         .filter(|line| !line.is_empty())
-        .map(|line| line.split(",").map(|part| part.parse::<u32>().unwrap()).collect())
-        .collect();
+        .map(|line| {
+            line.split(",")
+                .map(|part| parse_integer(input, part))
+                .collect()
+        })
+        .collect::<Result<_, ParseError>>()?;
+
+    Ok((ordering, docs))
+}
+
+pub fn calculate(input: &str, correct: bool) -> Result<usize, ParseError> {
+    let (ordering, docs) = parse_input(input)?;
 
     let final_docs: Vec<Vec<u32>> = if correct {
-        docs.into_iter().filter(|doc| ordering_is_correct(&doc, &ordering)).collect()
+        docs.into_iter()
+            .filter(|doc| ordering_is_correct(&doc, &ordering))
+            .collect()
     } else {
-        docs
-            .into_iter()
+        docs.into_iter()
             .filter(|doc| !ordering_is_correct(&doc, &ordering))
             .map(|doc| fix_doc(&doc, &ordering))
             .collect()
     };
 
-    final_docs.iter().map(|doc| {
-        let middle = (doc.len() as f32 / 2.0).floor() as usize;
+    Ok(final_docs
+        .iter()
+        .map(|doc| {
+            let middle = (doc.len() as f32 / 2.0).floor() as usize;
 
-        *doc.get(middle).unwrap() as usize
-    }).sum()
+            *doc.get(middle).unwrap() as usize
+        })
+        .sum())
 }
 
 fn fix_doc(doc: &[u32], ordering: &[(u32, u32)]) -> Vec<u32> {
@@ -77,22 +99,55 @@ fn ordering_is_correct(doc: &[u32], ordering: &[(u32, u32)]) -> bool {
     true
 }
 
+pub struct Day5;
+
+impl Solution for Day5 {
+    fn year(&self) -> u16 {
+        2024
+    }
+
+    fn day(&self) -> u8 {
+        5
+    }
+
+    fn title(&self) -> &'static str {
+        "Print Queue"
+    }
+
+    fn fixtures(&self) -> &'static [(&'static str, &'static str)] {
+        &[("example", include_str!("test_input.txt"))]
+    }
+
+    fn parse(&self, input: &str) -> Option<Result<(), ParseError>> {
+        Some(parse_input(input).map(drop))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, ParseError> {
+        Ok(calculate(input, true)?.into())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, ParseError> {
+        Ok(calculate(input, false)?.into())
+    }
+}
+
 #[cfg(test)]
 mod day5_tests {
+    use crate::input::puzzle_input;
     use crate::questions_2024::day5::calculate;
 
     #[test]
     fn test_input_challenge_1() {
         let input = include_str!("test_input.txt");
-        let result = calculate(input, true);
+        let result = calculate(input, true).unwrap();
 
         assert_eq!(result, 143);
     }
 
     #[test]
     fn input_challenge_1() {
-        let input = include_str!("input.txt");
-        let result = calculate(input, true);
+        let input = &puzzle_input(2024, 5);
+        let result = calculate(input, true).unwrap();
 
         println!("{result}")
     }
@@ -100,16 +155,16 @@ mod day5_tests {
     #[test]
     fn test_input_challenge_2() {
         let input = include_str!("test_input.txt");
-        let result = calculate(input, false);
+        let result = calculate(input, false).unwrap();
 
         assert_eq!(result, 123);
     }
 
     #[test]
     fn input_challenge_2() {
-        let input = include_str!("input.txt");
-        let result = calculate(input, false);
+        let input = &puzzle_input(2024, 5);
+        let result = calculate(input, false).unwrap();
 
         println!("{result}")
     }
-}
\ No newline at end of file
+}
@@ -1,216 +1,834 @@
-use crate::questions_2024::day6::Direction::{Down, Left, Right, Up};
-use crate::questions_2024::day6::Tile::{Empty, Obstacle};
-use rayon::prelude::IntoParallelIterator;
+use crate::error::{ParseError, ParseErrorKind};
+use crate::geometry::{Direction, Point};
+use crate::grid::Grid;
+use crate::questions_2024::day6::Tile::{Empty, Exit, Obstacle};
+use crate::solution::{Answer, Solution};
+use rayon::prelude::*;
 use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Formatter};
 
-#[derive(Default, Copy, Clone, Hash, Eq, PartialEq)]
-struct Pos {
-    x: isize,
-    y: isize,
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Tile {
+    /// The guard turns right in front of it
+    Obstacle,
+    Empty,
+    /// A way out in the middle of the map, the guard leaves through it like over the edge
+    Exit,
 }
 
-impl Pos {
-    fn apply_direction(mut self, direction: Direction) -> Self {
-        match direction {
-            Up => self.y -= 1,
-            Direction::Down => self.y += 1,
-            Direction::Left => self.x -= 1,
-            Direction::Right => self.x += 1,
-        };
+/// The guard facing up, right, down and left, in the order of `Direction::ALL`
+const GUARD_SYMBOLS: [char; 4] = ['^', '>', 'v', '<'];
 
-        self
-    }
+/// Which tile each character of a map stands for, so maps drawn with other characters or with
+/// extra tiles can be read. The guard symbols are always known and can't be redefined.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Legend {
+    tiles: HashMap<char, Tile>,
 }
 
-#[derive(Copy, Clone)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
+/// The characters from the puzzle, `#` and `.`
+impl Default for Legend {
+    fn default() -> Self {
+        Self {
+            tiles: HashMap::from([('#', Obstacle), ('.', Empty)]),
+        }
+    }
 }
 
-impl Direction {
-    fn tilt_90(mut self) -> Self {
-        match self {
-            Up => Right,
-            Right => Down,
-            Down => Left,
-            Left => Up,
+impl Legend {
+    /// Adds a character used by a variant of the puzzle, e.g. `O` for obstacles placed later
+    ///
+    /// Errors are reported as if the symbol was an input of its own.
+    pub fn with(mut self, symbol: char, tile: Tile) -> Result<Self, ParseError> {
+        if GUARD_SYMBOLS.contains(&symbol) {
+            let symbol = symbol.to_string();
+
+            return Err(ParseError::new(
+                &symbol,
+                &symbol,
+                ParseErrorKind::Invalid("guard symbols can't be redefined"),
+            ));
         }
+
+        self.tiles.insert(symbol, tile);
+
+        Ok(self)
     }
 }
 
-#[derive(Copy, Clone, Eq, PartialEq)]
-enum Tile {
-    Obstacle,
-    Empty,
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum PatrolOutcome {
+    /// The guard walked off the map, the number is how many steps that took
+    Exited(usize),
+    /// The guard walks in circles, `Looped(cycle_start, cycle_len)` means the first lap started
+    /// after `cycle_start` steps and every lap takes `cycle_len` steps. A guard boxed in by
+    /// obstacles loops with a length of 0.
+    Looped(usize, usize),
+}
+
+#[derive(Debug, Clone)]
+struct Patrol {
+    outcome: PatrolOutcome,
+    /// Every position and direction the guard moved from, with the step it did so first
+    states: HashMap<(Point, Direction), usize>,
+}
+
+impl Patrol {
+    fn visited_positions(&self) -> HashSet<Point> {
+        self.states.keys().map(|(pos, _)| *pos).collect()
+    }
+
+    /// Every step of the guard in order, the position they left and the direction they went
+    fn moves(&self) -> Vec<(Point, Direction)> {
+        let mut moves: Vec<_> = self.states.iter().collect();
+        moves.sort_by_key(|(_, steps)| **steps);
+
+        moves.into_iter().map(|(state, _)| *state).collect()
+    }
 }
 
-#[derive(Clone)]
+#[derive(Debug, Clone)]
 struct Map {
-    recursion_detected: bool,
-    visited_positions: HashMap<Pos, usize>,
     guard_direction: Direction,
-    guard_pos: Pos,
-    tiles: Vec<Vec<Tile>>,
+    guard_pos: Point,
+    tiles: Grid<Tile>,
+    /// The characters the map was drawn with, so it's rendered the way it was read
+    symbols: Grid<char>,
 }
 
-const RECURSION_TRESHOLD: usize = 10;
-
 impl Map {
-    fn new(input: &str) -> Self {
-        let mut guard_pos = Pos::default();
-        let tiles = input
-            .split("\n")
-            .filter(|line| !line.is_empty())
-            .enumerate()
-            .map(|(y, line)| {
-                line.chars()
-                    .into_iter()
-                    .enumerate()
-                    .map(|(x, char)| match char {
-                        '^' => {
-                            guard_pos = Pos {
-                                x: x as isize,
-                                y: y as isize,
-                            };
-                            Empty
-                        }
-                        '#' => Obstacle,
-                        _ => Empty,
-                    })
-                    .collect()
-            })
-            .collect();
+    fn new(input: &str) -> Result<Self, ParseError> {
+        Self::with_legend(input, &Legend::default())
+    }
 
-        Self {
-            recursion_detected: false,
-            visited_positions: HashMap::new(),
-            guard_direction: Up,
+    /// There has to be exactly one guard, they stand on an empty tile
+    fn with_legend(input: &str, legend: &Legend) -> Result<Self, ParseError> {
+        let symbols = Grid::parse(input, Ok)?;
+        let grid = Grid::parse(input, |char| {
+            if GUARD_SYMBOLS.contains(&char) {
+                return Ok(Empty);
+            }
+
+            legend
+                .tiles
+                .get(&char)
+                .copied()
+                .ok_or(ParseErrorKind::UnknownToken("tile"))
+        })?;
+
+        let mut guards = input.match_indices(GUARD_SYMBOLS);
+
+        let Some((offset, guard)) = guards.next() else {
+            return Err(ParseError::new(
+                input,
+                &input[input.len()..],
+                ParseErrorKind::MissingField("guard"),
+            ));
+        };
+
+        if let Some((_, other_guard)) = guards.next() {
+            return Err(ParseError::new(
+                input,
+                other_guard,
+                ParseErrorKind::Invalid("more than one guard"),
+            ));
+        }
+
+        let guard_symbol = guard.chars().next().unwrap();
+        let guard_direction = Direction::ALL[GUARD_SYMBOLS
+            .iter()
+            .position(|symbol| *symbol == guard_symbol)
+            .unwrap()];
+
+        // Empty lines are skipped by the grid, so the row is counted the same way
+        let line_start = input[..offset].rfind('\n').map_or(0, |index| index + 1);
+        let guard_pos = Point::new(
+            input[line_start..offset].chars().count() as isize,
+            input[..line_start]
+                .lines()
+                .filter(|line| !line.is_empty())
+                .count() as isize,
+        );
+
+        Ok(Self {
+            guard_direction,
             guard_pos,
-            tiles,
+            tiles: grid,
+            symbols,
+        })
+    }
+
+    fn get_tile(&self, pos: &Point) -> Option<&Tile> {
+        self.tiles.get(*pos)
+    }
+
+    /// Whether stepping onto `pos` takes the guard off the map
+    fn is_way_out(&self, pos: &Point) -> bool {
+        matches!(self.get_tile(pos), None | Some(Exit))
+    }
+
+    /// Turns right until the way ahead is clear, `None` when the guard is boxed in
+    fn guard_turn(&self, pos: Point, mut direction: Direction) -> Option<Direction> {
+        for _ in 0..4 {
+            if self.get_tile(&pos.step(direction)) != Some(&Obstacle) {
+                return Some(direction);
+            }
+
+            direction = direction.turn_right();
         }
+
+        None
     }
 
-    fn get_tile(&self, pos: &Pos) -> Option<&Tile> {
-        Some(self.tiles.get(pos.y as usize)?.get(pos.x as usize)?)
+    /// Follows the guard until they leave the map or come back to a position they already
+    /// left in the same direction, from there on they can only repeat themselves
+    fn patrol(&self) -> Patrol {
+        let mut states = HashMap::new();
+        let (mut pos, mut direction) = (self.guard_pos, self.guard_direction);
+        let mut steps = 0;
+
+        let outcome = loop {
+            let Some(turned) = self.guard_turn(pos, direction) else {
+                break PatrolOutcome::Looped(steps, 0);
+            };
+            direction = turned;
+
+            if let Some(&cycle_start) = states.get(&(pos, direction)) {
+                break PatrolOutcome::Looped(cycle_start, steps - cycle_start);
+            }
+            states.insert((pos, direction), steps);
+
+            pos = pos.step(direction);
+            steps += 1;
+
+            if self.is_way_out(&pos) {
+                break PatrolOutcome::Exited(steps);
+            }
+        };
+
+        Patrol { outcome, states }
     }
 
-    fn guard_step(&mut self) -> Result<(), ()> {
-        if let None = self.get_tile(&self.guard_pos) {
-            return Err(());
+    /// For every cell and direction, the last cell before the next obstacle that way, `None`
+    /// when the guard would walk off the map or through an exit instead
+    fn jump_table(&self) -> Grid<[Option<Point>; 4]> {
+        let mut jumps = Grid::new(self.tiles.width(), self.tiles.height(), [None; 4]);
+        let cells: Vec<Point> = self.tiles.iter().map(|(pos, _)| pos).collect();
+
+        // The cell ahead has to be done first, so cells are visited in reading order when going
+        // up or left and backwards when going down or right
+        let passes = [
+            (cells.clone(), [Direction::Up, Direction::Left]),
+            (
+                cells.into_iter().rev().collect(),
+                [Direction::Down, Direction::Right],
+            ),
+        ];
+
+        for (cells, directions) in passes {
+            for pos in cells {
+                for direction in directions {
+                    let ahead = pos.step(direction);
+                    let jump = match self.get_tile(&ahead) {
+                        None | Some(Exit) => None,
+                        Some(Obstacle) => Some(pos),
+                        Some(Empty) => {
+                            jumps[(ahead.x as usize, ahead.y as usize)][direction as usize]
+                        }
+                    };
+
+                    jumps[(pos.x as usize, pos.y as usize)][direction as usize] = jump;
+                }
+            }
         }
 
+        jumps
+    }
+
+    /// Whether the guard ends up in a loop with an extra obstacle at `obstacle`
+    ///
+    /// Walks from turn to turn with the jump table instead of step by step, `seen` is only
+    /// borrowed so every thread can reuse its own.
+    fn loops_with_obstacle(
+        &self,
+        jumps: &Grid<[Option<Point>; 4]>,
+        obstacle: Point,
+        seen: &mut SeenStates,
+    ) -> bool {
+        seen.clear();
+
+        let is_blocked = |pos: Point| pos == obstacle || self.get_tile(&pos) == Some(&Obstacle);
+        let (mut pos, mut direction) = (self.guard_pos, self.guard_direction);
+
         loop {
-            if let Some(Obstacle) =
-                self.get_tile(&self.guard_pos.apply_direction(self.guard_direction))
-            {
-                self.guard_direction = self.guard_direction.tilt_90();
-            } else {
-                if *self.visited_positions.entry(self.guard_pos).or_default() > RECURSION_TRESHOLD {
-                    self.recursion_detected = true;
+            let mut turns = 0;
+            while is_blocked(pos.step(direction)) {
+                direction = direction.turn_right();
+                turns += 1;
+
+                if turns == 4 {
+                    return true;
                 }
+            }
 
-                break;
+            if !seen.insert(pos, direction) {
+                return true;
             }
+
+            let jump = jumps[(pos.x as usize, pos.y as usize)][direction as usize];
+
+            // The extra obstacle cuts the jump short when it's in the way
+            let offset = obstacle - pos;
+            let ahead = Point::from(direction);
+            let in_line = offset.x * ahead.y == offset.y * ahead.x
+                && offset.x * ahead.x + offset.y * ahead.y > 0;
+
+            let cut_short = in_line
+                && match jump {
+                    Some(jump) => pos.manhattan(obstacle) <= pos.manhattan(jump),
+                    // The guard walks off the edge past any obstacle, but an exit can come first
+                    None => !(1..pos.manhattan(obstacle) as isize)
+                        .any(|distance| self.get_tile(&(pos + ahead * distance)) == Some(&Exit)),
+                };
+
+            pos = match jump {
+                _ if cut_short => obstacle - ahead,
+                Some(jump) => jump,
+                None => return false,
+            };
         }
+    }
+}
 
-        *self.visited_positions.entry(self.guard_pos).or_default() += 1;
+/// Which ways the guard already left each cell, one bit per direction
+struct SeenStates {
+    directions: Grid<u8>,
+    /// Cells with bits set, so clearing doesn't have to go over the whole map
+    touched: Vec<Point>,
+}
 
-        self.guard_pos = self.guard_pos.apply_direction(self.guard_direction);
+impl SeenStates {
+    fn new(map: &Map) -> Self {
+        Self {
+            directions: Grid::new(map.tiles.width(), map.tiles.height(), 0),
+            touched: Vec::new(),
+        }
+    }
+
+    /// `false` when the guard already left `pos` going `direction`
+    fn insert(&mut self, pos: Point, direction: Direction) -> bool {
+        let directions = self.directions.get_mut(pos).unwrap();
+        let bit = 1 << direction as u8;
+
+        if *directions & bit != 0 {
+            return false;
+        }
 
-        Ok(())
+        if *directions == 0 {
+            self.touched.push(pos);
+        }
+        *directions |= bit;
+
+        true
+    }
+
+    fn clear(&mut self) {
+        for pos in self.touched.drain(..) {
+            *self.directions.get_mut(pos).unwrap() = 0;
+        }
     }
 }
 
-fn calculate(input: &str) -> usize {
-    let mut map = Map::new(input);
+/// How many positions the guard visits before leaving the map, `None` when they never leave
+pub fn calculate(input: &str) -> Result<Option<usize>, ParseError> {
+    calculate_with_legend(input, &Legend::default())
+}
+
+/// Like `calculate`, for maps drawn with other characters
+pub fn calculate_with_legend(input: &str, legend: &Legend) -> Result<Option<usize>, ParseError> {
+    let map = Map::with_legend(input, legend)?;
+    let patrol = map.patrol();
+
+    Ok(match patrol.outcome {
+        PatrolOutcome::Exited(_) => Some(patrol.visited_positions().len()),
+        PatrolOutcome::Looped(..) => None,
+    })
+}
+
+/// The positions where an extra obstacle traps the guard in a loop
+fn find_loop_obstacles(map: &Map) -> Vec<Point> {
+    let jumps = map.jump_table();
+
+    // An obstacle can only change the route when it's placed somewhere on it
+    let candidates: Vec<Point> = map
+        .patrol()
+        .visited_positions()
+        .into_iter()
+        .filter(|pos| *pos != map.guard_pos)
+        .collect();
+
+    candidates
+        .into_par_iter()
+        .map_init(
+            || SeenStates::new(map),
+            |seen, obstacle| {
+                map.loops_with_obstacle(&jumps, obstacle, seen)
+                    .then_some(obstacle)
+            },
+        )
+        .flatten()
+        .collect()
+}
 
-    solve_map(&mut map, false);
+pub fn calculate_obstacles(input: &str) -> Result<usize, ParseError> {
+    calculate_obstacles_with_legend(input, &Legend::default())
+}
+
+/// Like `calculate_obstacles`, for maps drawn with other characters
+pub fn calculate_obstacles_with_legend(input: &str, legend: &Legend) -> Result<usize, ParseError> {
+    let map = Map::with_legend(input, legend)?;
+
+    Ok(find_loop_obstacles(&map).len())
+}
+
+fn guard_symbol(direction: Direction) -> char {
+    GUARD_SYMBOLS[direction as usize]
+}
 
-    map.visited_positions.len()
+/// The map like the pictures in the puzzle, the route so far is drawn with `|`, `-` and `+` over
+/// the empty tiles, the guard's start with their symbol and every obstacle in `obstacles` with `O`.
+/// Everything else keeps the character it was read from.
+struct RouteOnMap<'a> {
+    map: &'a Map,
+    moves: &'a [(Point, Direction)],
+    obstacles: &'a [Point],
+    /// Where the guard stands and which way they face, left out once they walked off the map
+    guard: Option<(Point, Direction)>,
 }
 
-fn solve_map(map: &mut Map, stop_on_recursion: bool) {
-    loop {
-        match map.guard_step() {
-            Ok(_) => {
-                if map.recursion_detected && stop_on_recursion {
-                    break;
+impl Display for RouteOnMap<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut cells = self.map.symbols.clone();
+        let mut lines: Grid<Option<char>> = self.map.tiles.map(|_| None);
+
+        // Each cell is walked in the direction of the move leaving it and the move entering it
+        for &(pos, direction) in self.moves {
+            let line = match direction {
+                Direction::Up | Direction::Down => '|',
+                Direction::Left | Direction::Right => '-',
+            };
+
+            for pos in [pos, pos.step(direction)] {
+                if self.map.get_tile(&pos) != Some(&Empty) {
+                    continue;
                 }
+
+                let cell = lines.get_mut(pos).unwrap();
+                *cell = match *cell {
+                    None => Some(line),
+                    Some(drawn) if drawn == line => Some(line),
+                    Some(_) => Some('+'),
+                };
+            }
+        }
+
+        for (pos, line) in lines.iter() {
+            if let Some(line) = line {
+                *cells.get_mut(pos).unwrap() = *line;
             }
-            Err(_) => break,
         }
+
+        let mut symbols = vec![(self.map.guard_pos, guard_symbol(self.map.guard_direction))];
+        symbols.extend(self.obstacles.iter().map(|obstacle| (*obstacle, 'O')));
+        symbols.extend(
+            self.guard
+                .map(|(pos, direction)| (pos, guard_symbol(direction))),
+        );
+
+        for (pos, symbol) in symbols {
+            if let Some(cell) = cells.get_mut(pos) {
+                *cell = symbol;
+            }
+        }
+
+        write!(f, "{cells}")
+    }
+}
+
+/// The guard's whole route, with an `O` on every obstacle that would trap them in a loop when
+/// `with_obstacles` is set
+pub fn render_route(input: &str, with_obstacles: bool) -> Result<String, ParseError> {
+    render_route_with_legend(input, &Legend::default(), with_obstacles)
+}
+
+/// Like `render_route`, for maps drawn with other characters
+pub fn render_route_with_legend(
+    input: &str,
+    legend: &Legend,
+    with_obstacles: bool,
+) -> Result<String, ParseError> {
+    let map = Map::with_legend(input, legend)?;
+    let moves = map.patrol().moves();
+    let obstacles = match with_obstacles {
+        true => find_loop_obstacles(&map),
+        false => Vec::new(),
+    };
+
+    Ok(RouteOnMap {
+        map: &map,
+        moves: &moves,
+        obstacles: &obstacles,
+        guard: None,
     }
+    .to_string())
 }
 
-fn calculate_obstacles(input: &str) -> usize {
-    let map = Map::new(input);
+/// One picture per step of the guard, for animating the patrol
+pub fn render_frames(input: &str) -> Result<Vec<String>, ParseError> {
+    render_frames_with_legend(input, &Legend::default())
+}
 
-    let mut solved_map = map.clone();
+/// Like `render_frames`, for maps drawn with other characters
+pub fn render_frames_with_legend(input: &str, legend: &Legend) -> Result<Vec<String>, ParseError> {
+    let map = Map::with_legend(input, legend)?;
+    let moves = map.patrol().moves();
+
+    Ok((0..=moves.len())
+        .map(|steps| {
+            let guard = match steps {
+                0 => (map.guard_pos, map.guard_direction),
+                _ => {
+                    let (pos, direction) = moves[steps - 1];
+                    (pos.step(direction), direction)
+                }
+            };
 
-    solve_map(&mut solved_map, false);
+            RouteOnMap {
+                map: &map,
+                moves: &moves[..steps],
+                obstacles: &[],
+                guard: Some(guard),
+            }
+            .to_string()
+        })
+        .collect())
+}
 
-    solved_map
-        .visited_positions
-        .iter()
-        .map(|(pos, _)| pos)
-        .filter(|pos| **pos != map.guard_pos)
-        .map(|pos| {
-            let mut test_map = map.clone();
+pub struct Day6;
 
-            *test_map
-                .tiles
-                .get_mut(pos.y as usize)
-                .unwrap()
-                .get_mut(pos.x as usize)
-                .unwrap() = Obstacle;
+impl Solution for Day6 {
+    fn year(&self) -> u16 {
+        2024
+    }
 
-            solve_map(&mut test_map, true);
+    fn day(&self) -> u8 {
+        6
+    }
 
-            if test_map.recursion_detected {
-                return 1;
-            }
+    fn title(&self) -> &'static str {
+        "Guard Gallivant"
+    }
 
-            return 0;
-        })
-        .sum()
+    fn fixtures(&self) -> &'static [(&'static str, &'static str)] {
+        &[("example", include_str!("test_input.txt"))]
+    }
+
+    fn parse(&self, input: &str) -> Option<Result<(), ParseError>> {
+        Some(Map::new(input).map(drop))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, ParseError> {
+        // A guard walking in circles never finishes the route, so there's nothing to count
+        Ok(calculate(input)?.map_or(Answer::Unsolved, Answer::from))
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, ParseError> {
+        Ok(calculate_obstacles(input)?.into())
+    }
+
+    fn render(&self, part: u8, input: &str) -> Option<Result<String, ParseError>> {
+        match part {
+            1 => Some(render_route(input, false)),
+            2 => Some(render_route(input, true)),
+            _ => None,
+        }
+    }
+
+    /// The guard's patrol step by step, the loop obstacles of part 2 aren't animated
+    fn render_frames(&self, part: u8, input: &str) -> Option<Result<Vec<String>, ParseError>> {
+        match part {
+            1 => Some(render_frames(input)),
+            _ => None,
+        }
+    }
 }
 
 #[cfg(test)]
 mod day6_tests {
-    use crate::questions_2024::day6::{calculate, calculate_obstacles};
+    use crate::geometry::{Direction, Point};
+    use crate::input::puzzle_input;
+    use crate::questions_2024::day6::{
+        calculate, calculate_obstacles, calculate_with_legend, render_frames,
+        render_frames_with_legend, render_route, render_route_with_legend, Legend, Map,
+        PatrolOutcome, RouteOnMap, SeenStates, Tile,
+    };
 
     #[test]
     fn test_input_challenge_1() {
         let input = include_str!("test_input.txt");
-        let result = calculate(input);
+        let result = calculate(input).unwrap();
 
-        assert_eq!(result, 41);
+        assert_eq!(result, Some(41));
     }
 
     #[test]
     fn input_challenge_1() {
-        let input = include_str!("input.txt");
-        let result = calculate(input);
+        let input = &puzzle_input(2024, 6);
+        let result = calculate(input).unwrap();
 
-        println!("{result}")
+        println!("{result:?}")
     }
 
     #[test]
     fn test_input_challenge_2() {
         let input = include_str!("test_input.txt");
-        let result = calculate_obstacles(input);
+        let result = calculate_obstacles(input).unwrap();
 
         assert_eq!(result, 6);
     }
 
     #[test]
     fn input_challenge_2() {
-        let input = include_str!("input.txt");
-        let result = calculate_obstacles(input);
+        let input = &puzzle_input(2024, 6);
+        let result = calculate_obstacles(input).unwrap();
 
         println!("{result}")
     }
+
+    #[test]
+    fn patrol_outcomes() {
+        let input = include_str!("test_input.txt");
+        let mut map = Map::new(input).unwrap();
+
+        assert_eq!(map.patrol().outcome, PatrolOutcome::Exited(45));
+
+        // The first of the obstacles from the puzzle text, the guard goes round a rectangle
+        // starting where they stand
+        *map.tiles.get_mut(Point::new(3, 6)).unwrap() = Tile::Obstacle;
+        assert_eq!(map.patrol().outcome, PatrolOutcome::Looped(0, 18));
+    }
+
+    #[test]
+    fn jumps_agree_with_walking() {
+        let input = include_str!("test_input.txt");
+        let map = Map::new(input).unwrap();
+        let jumps = map.jump_table();
+        let mut seen = SeenStates::new(&map);
+
+        assert_eq!(
+            jumps[(4, 6)][Direction::Up as usize],
+            Some(Point::new(4, 1))
+        );
+        assert_eq!(
+            jumps[(4, 6)][Direction::Left as usize],
+            Some(Point::new(2, 6))
+        );
+        assert_eq!(jumps[(4, 6)][Direction::Down as usize], None);
+
+        for (obstacle, tile) in map.tiles.iter() {
+            if *tile == Tile::Obstacle || obstacle == map.guard_pos {
+                continue;
+            }
+
+            let mut walked = map.clone();
+            *walked.tiles.get_mut(obstacle).unwrap() = Tile::Obstacle;
+            let looped = matches!(walked.patrol().outcome, PatrolOutcome::Looped(..));
+
+            assert_eq!(
+                map.loops_with_obstacle(&jumps, obstacle, &mut seen),
+                looped,
+                "obstacle at {obstacle:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn renders_routes_like_the_puzzle() {
+        let input = include_str!("test_input.txt");
+        let mut map = Map::new(input).unwrap();
+        *map.tiles.get_mut(Point::new(3, 6)).unwrap() = Tile::Obstacle;
+
+        let moves = map.patrol().moves();
+        let route = RouteOnMap {
+            map: &map,
+            moves: &moves,
+            obstacles: &[Point::new(3, 6)],
+            guard: None,
+        };
+
+        // The first obstacle from the puzzle text and the loop it causes
+        assert_eq!(
+            route.to_string(),
+            "\
+....#.....
+....+---+#
+....|...|.
+..#.|...|.
+....|..#|.
+....|...|.
+.#.O^---+.
+........#.
+#.........
+......#...
+"
+        );
+
+        let route = render_route(input, true).unwrap();
+        assert_eq!(route.matches('O').count(), 6);
+        assert!(route.contains(".+----OO#."));
+    }
+
+    #[test]
+    fn renders_a_frame_per_step() {
+        let input = include_str!("test_input.txt");
+
+        let frames = render_frames(input).unwrap();
+
+        assert_eq!(frames.len(), 46);
+        assert!(frames[0].contains("..........\n.#..^....."));
+        assert!(frames[2].contains("....^..#..\n....|.....\n.#..^....."));
+        assert!(!frames[45].contains('v'));
+    }
+
+    #[test]
+    fn parses_every_guard_orientation() {
+        let map = Map::new("...\n\n.#.\n..<\n").unwrap();
+        assert_eq!(map.guard_pos, Point::new(2, 2));
+        assert_eq!(map.guard_direction, Direction::Left);
+
+        for (symbol, direction) in ['^', '>', 'v', '<'].into_iter().zip(Direction::ALL) {
+            let map = Map::new(&format!("#..\n.{symbol}.\n")).unwrap();
+
+            assert_eq!(map.guard_pos, Point::new(1, 1));
+            assert_eq!(map.guard_direction, direction);
+            assert_eq!(map.get_tile(&map.guard_pos), Some(&Tile::Empty));
+        }
+
+        assert_eq!(calculate("....\n.#..\n>...\n").unwrap(), Some(4));
+        assert_eq!(calculate("....\n.#..\n.v..\n").unwrap(), Some(1));
+    }
+
+    #[test]
+    fn rejects_missing_and_extra_guards() {
+        let error = Map::new("#..\n...\n").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 3, column 1: missing guard, found ''"
+        );
+
+        let error = Map::new("#.^\n.v.\n").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 2: more than one guard, found 'v'"
+        );
+
+        let error = Map::new("#.^\n.O.\n").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 2: unknown tile, found 'O'"
+        );
+    }
+
+    #[test]
+    fn parses_extra_tile_kinds() {
+        let legend = Legend::default()
+            .with('O', Tile::Obstacle)
+            .and_then(|legend| legend.with('X', Tile::Empty))
+            .unwrap();
+
+        let map = Map::with_legend("..O.\nX.^X\n", &legend).unwrap();
+
+        assert_eq!(map.get_tile(&Point::new(2, 0)), Some(&Tile::Obstacle));
+        assert_eq!(map.get_tile(&Point::new(0, 1)), Some(&Tile::Empty));
+        assert_eq!(map.guard_direction, Direction::Up);
+        assert_eq!(
+            calculate_with_legend("..O.\nX.^X\n", &legend).unwrap(),
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn leaves_through_exits() {
+        let legend = Legend::default().with('E', Tile::Exit).unwrap();
+        let input = "#E..\n....\n.^..\n";
+
+        let map = Map::with_legend(input, &legend).unwrap();
+        assert_eq!(map.patrol().outcome, PatrolOutcome::Exited(2));
+        assert_eq!(map.jump_table()[(1, 2)][Direction::Up as usize], None);
+
+        assert_eq!(calculate_with_legend(input, &legend).unwrap(), Some(2));
+        assert_eq!(calculate(&input.replace('E', ".")).unwrap(), Some(3));
+
+        // The jumps have to stop at exits too, wherever one is on the example map
+        let example = include_str!("test_input.txt");
+
+        for (offset, _) in example.match_indices('.') {
+            let mut input = example.to_string();
+            input.replace_range(offset..offset + 1, "E");
+
+            let map = Map::with_legend(&input, &legend).unwrap();
+            let jumps = map.jump_table();
+            let mut seen = SeenStates::new(&map);
+
+            for (obstacle, tile) in map.tiles.iter() {
+                if *tile != Tile::Empty || obstacle == map.guard_pos {
+                    continue;
+                }
+
+                let mut walked = map.clone();
+                *walked.tiles.get_mut(obstacle).unwrap() = Tile::Obstacle;
+                let looped = matches!(walked.patrol().outcome, PatrolOutcome::Looped(..));
+
+                assert_eq!(
+                    map.loops_with_obstacle(&jumps, obstacle, &mut seen),
+                    looped,
+                    "exit at {offset}, obstacle at {obstacle:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn renders_with_the_legend() {
+        let legend = Legend::default()
+            .with('X', Tile::Obstacle)
+            .and_then(|legend| legend.with('E', Tile::Exit))
+            .unwrap();
+
+        let route = render_route_with_legend(".X..\n...E\n.^..\n", &legend, false).unwrap();
+        assert_eq!(route, ".X..\n.+-E\n.^..\n");
+
+        let frames = render_frames_with_legend(".X..\n...E\n.^..\n", &legend).unwrap();
+        assert_eq!(frames.len(), 4);
+        assert_eq!(frames[2], ".X..\n.+>E\n.^..\n");
+    }
+
+    #[test]
+    fn rejects_redefined_guard_symbols() {
+        let error = Legend::default().with('^', Tile::Obstacle).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 1: guard symbols can't be redefined, found '^'"
+        );
+    }
+
+    #[test]
+    fn boxed_in_guard_loops_in_place() {
+        let map = Map::new(".#.\n#^#\n.#.\n").unwrap();
+
+        assert_eq!(map.patrol().outcome, PatrolOutcome::Looped(0, 0));
+
+        assert_eq!(calculate(".#.\n#^#\n.#.\n"), Ok(None));
+    }
 }
@@ -1,127 +1,968 @@
-use crate::questions_2024::day7::Operator::{Concatenate, Mul, Plus};
-use std::collections::HashMap;
-use std::fmt::format;
-use std::io::BufRead;
-
-fn calculate(input: &str) -> usize {
-    let mut solver = Solver::new();
-
-    input
-        .split("\n")
-        .filter(|line| !line.is_empty())
-        .map(|line| {
-            let splitted_line: Vec<&str> = line.split(":").collect();
-            let inputs: Vec<usize> = splitted_line
-                .get(1)
-                .unwrap()
-                .split(" ")
-                .filter(|input| !input.is_empty())
-                .map(|input| input.parse::<usize>().unwrap())
-                .collect();
-
-            (
-                splitted_line.get(0).unwrap().parse::<usize>().unwrap(),
-                inputs,
-            )
-        })
-        .filter(|line| solver.is_line_correct(&line))
-        .map(|line| line.0)
-        .sum()
+use crate::bigint::BigUint;
+use crate::error::{expect_field, parse_integer, ParseError, ParseErrorKind};
+use crate::questions_2024::day7::Operator::{Concatenate, Div, Minus, Mul, Plus, Pow};
+use crate::solution::{Answer, Solution};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{Debug, Display, Formatter};
+use std::hash::Hash;
+use std::iter::successors;
+use std::str::FromStr;
+
+/// A numeric backend for the solver
+///
+/// Arithmetic that doesn't fit gives `None`, so the branch that needed it is dropped instead of
+/// overflowing.
+pub trait Number: Clone + Ord + Hash + Debug + Display + FromStr {
+    fn from_u32(number: u32) -> Self;
+    fn checked_add(&self, other: &Self) -> Option<Self>;
+    fn checked_sub(&self, other: &Self) -> Option<Self>;
+    fn checked_mul(&self, other: &Self) -> Option<Self>;
+    /// Quotient and remainder, `None` when dividing by zero
+    fn checked_div_rem(&self, other: &Self) -> Option<(Self, Self)>;
+    /// `None` when the number is too large to be an exponent
+    fn to_u32(&self) -> Option<u32>;
+}
+
+macro_rules! impl_number {
+    ($($number:ty),+) => {
+        $(
+            impl Number for $number {
+                fn from_u32(number: u32) -> Self {
+                    number as Self
+                }
+
+                fn checked_add(&self, other: &Self) -> Option<Self> {
+                    <$number>::checked_add(*self, *other)
+                }
+
+                fn checked_sub(&self, other: &Self) -> Option<Self> {
+                    <$number>::checked_sub(*self, *other)
+                }
+
+                fn checked_mul(&self, other: &Self) -> Option<Self> {
+                    <$number>::checked_mul(*self, *other)
+                }
+
+                fn checked_div_rem(&self, other: &Self) -> Option<(Self, Self)> {
+                    Some((self.checked_div(*other)?, self % other))
+                }
+
+                fn to_u32(&self) -> Option<u32> {
+                    u32::try_from(*self).ok()
+                }
+            }
+        )+
+    };
+}
+
+impl_number!(usize, u64, u128);
+
+/// Never overflows, every branch is followed until it can't make the answer
+impl Number for BigUint {
+    fn from_u32(number: u32) -> Self {
+        BigUint::from(u64::from(number))
+    }
+
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        Some(self + other)
+    }
+
+    fn checked_sub(&self, other: &Self) -> Option<Self> {
+        BigUint::checked_sub(self, other)
+    }
+
+    fn checked_mul(&self, other: &Self) -> Option<Self> {
+        Some(self * other)
+    }
+
+    fn checked_div_rem(&self, other: &Self) -> Option<(Self, Self)> {
+        self.div_rem(other)
+    }
+
+    fn to_u32(&self) -> Option<u32> {
+        u32::try_from(self.to_u64()?).ok()
+    }
+}
+
+fn parse_line<N: Number>(input: &str, line: &str) -> Result<(N, Vec<N>), ParseError> {
+    let splitted_line: Vec<&str> = line.split(":").collect();
+    let numbers = expect_field(input, line, splitted_line.get(1).copied(), "numbers")?;
+
+    let inputs: Vec<N> = numbers
+        .split(" ")
+        .filter(|input| !input.is_empty())
+        .map(|number| parse_integer(input, number))
+        .collect::<Result<_, ParseError>>()?;
+
+    if inputs.is_empty() {
+        return Err(ParseError::new(
+            input,
+            numbers,
+            ParseErrorKind::MissingField("numbers"),
+        ));
+    }
+
+    let answer = expect_field(input, line, splitted_line.first().copied(), "answer")?;
+
+    Ok((parse_integer(input, answer)?, inputs))
+}
+
+fn lines(input: &str) -> impl Iterator<Item = &str> {
+    input.split("\n").filter(|line| !line.is_empty())
+}
+
+fn parse_equations<N: Number>(input: &str) -> Result<Vec<(N, Vec<N>)>, ParseError> {
+    lines(input).map(|line| parse_line(input, line)).collect()
 }
 
-#[derive(Clone, Copy, Debug)]
-enum Operator {
+/// Every line of the input with the operators that make it, for checking answers by hand
+pub fn explain<N: Number>(input: &str, operators: &[Operator]) -> Result<String, ParseError> {
+    let solver = Solver::new(operators);
+    let mut explanation = String::new();
+
+    for text in lines(input) {
+        let line = parse_line::<N>(input, text)?;
+        let (answer, inputs) = &line;
+
+        match solver.witness(&line) {
+            Some(operators) => {
+                // The solver puts an operator between every two numbers, so this can't fail
+                let equation = Equation::new(answer, inputs, &operators).map_err(|_| {
+                    ParseError::new(input, text, ParseErrorKind::Invalid("operators don't fit"))
+                })?;
+                let count = solver.count_solutions(&line);
+
+                let ways = if count == 1 { "way" } else { "ways" };
+
+                explanation.push_str(&format!("{equation} ({count} {ways})\n"));
+            }
+            None => {
+                let numbers: Vec<String> = inputs.iter().map(N::to_string).collect();
+
+                explanation.push_str(&format!("{answer}: {} can't be made\n", numbers.join(" ")));
+            }
+        }
+    }
+
+    Ok(explanation)
+}
+
+/// The operators of part 1, part 2 adds concatenation
+pub const PART_1_OPERATORS: &[Operator] = &[Plus, Mul];
+pub const PART_2_OPERATORS: &[Operator] = &[Plus, Mul, Concatenate];
+
+/// Sum of the answers that can be made by putting `operators` between the numbers
+pub fn calculate(input: &str, operators: &[Operator]) -> Result<usize, ParseError> {
+    calculate_with::<usize>(input, operators)
+}
+
+/// Like `calculate`, with the numbers of the input and everything made from them stored as `N`
+pub fn calculate_with<N: Number>(input: &str, operators: &[Operator]) -> Result<N, ParseError> {
+    let solver = Solver::new(operators);
+    let mut sum = N::from_u32(0);
+
+    for (line, equation) in lines(input).zip(parse_equations::<N>(input)?) {
+        if solver.is_line_correct(&equation) {
+            sum = sum.checked_add(&equation.0).ok_or_else(|| {
+                ParseError::new(input, line, ParseErrorKind::Invalid("sum doesn't fit"))
+            })?;
+        }
+    }
+
+    Ok(sum)
+}
+
+/// Operators are always evaluated left to right, ignoring precedence
+///
+/// Results can't go below zero, a subtraction that would is not a valid choice.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Operator {
     Plus,
     Mul,
+    /// Appends the digits of the right number to the left one, `12 || 345` is `12345`
     Concatenate,
+    Minus,
+    /// Integer division, rounding down
+    Div,
+    /// With a `BigUint` nothing stops large exponents, they just get slow
+    Pow,
+}
+
+impl Display for Operator {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Plus => "+",
+            Mul => "*",
+            Concatenate => "||",
+            Minus => "-",
+            Div => "/",
+            Pow => "^",
+        })
+    }
+}
+
+/// An answer with the operators between its numbers, shown like `190 = 10 * 19`
+pub struct Equation<'a, N> {
+    answer: &'a N,
+    inputs: &'a [N],
+    operators: &'a [Operator],
+}
+
+/// The operators of an `Equation` don't fit between its numbers
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct OperatorCountError {
+    pub inputs: usize,
+    pub operators: usize,
+}
+
+impl Display for OperatorCountError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} operators don't fit between {} numbers",
+            self.operators, self.inputs
+        )
+    }
+}
+
+impl std::error::Error for OperatorCountError {}
+
+impl<'a, N> Equation<'a, N> {
+    /// There has to be one operator less than there are numbers
+    pub fn new(
+        answer: &'a N,
+        inputs: &'a [N],
+        operators: &'a [Operator],
+    ) -> Result<Self, OperatorCountError> {
+        if operators.len() + 1 != inputs.len().max(1) {
+            return Err(OperatorCountError {
+                inputs: inputs.len(),
+                operators: operators.len(),
+            });
+        }
+
+        Ok(Self {
+            answer,
+            inputs,
+            operators,
+        })
+    }
+}
+
+impl<N: Display> Display for Equation<'_, N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} =", self.answer)?;
+
+        for (index, input) in self.inputs.iter().enumerate() {
+            if let Some(operator) = index.checked_sub(1).map(|index| self.operators[index]) {
+                write!(f, " {operator}")?;
+            }
+            write!(f, " {input}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The left operands an operator can be undone to
+#[derive(Debug, Eq, PartialEq)]
+enum Lefts<N> {
+    /// `count` numbers counting up from `first`
+    Consecutive { first: N, count: N },
+    /// Every number works, e.g. anything times zero is zero
+    Any,
+}
+
+impl<N: Number> Lefts<N> {
+    fn single(left: N) -> Self {
+        Lefts::Consecutive {
+            first: left,
+            count: N::from_u32(1),
+        }
+    }
+}
+
+/// Stops early when the numbers don't fit
+fn consecutive<N: Number>(first: N, count: N) -> impl Iterator<Item = N> {
+    let one = N::from_u32(1);
+
+    successors(Some((first, count)), move |(left, count)| {
+        let count = count.checked_sub(&one)?;
+
+        (count > N::from_u32(0)).then_some((left.checked_add(&one)?, count))
+    })
+    .map(|(left, _)| left)
+}
+
+/// Whether `value` is one of the `count` numbers counting up from `first`
+fn in_range<N: Number>(value: &N, first: &N, count: &N) -> bool {
+    value
+        .checked_sub(first)
+        .is_some_and(|offset| offset < *count)
+}
+
+/// `None` when the power doesn't fit or goes over `limit`
+fn checked_pow<N: Number>(base: &N, mut exponent: u32, limit: Option<&N>) -> Option<N> {
+    let within_limit = |number: N| limit.is_none_or(|limit| number <= *limit).then_some(number);
+    let mut base = base.clone();
+    let mut power = N::from_u32(1);
+
+    // Squaring the base only ever makes it bigger, so going over the limit with bits of the
+    // exponent left means the power will too
+    loop {
+        if exponent & 1 == 1 {
+            power = within_limit(power.checked_mul(&base)?)?;
+        }
+
+        exponent >>= 1;
+        if exponent == 0 {
+            return Some(power);
+        }
+
+        base = within_limit(base.checked_mul(&base)?)?;
+    }
 }
 
+/// The whole `n`th root of `number`, if there is one
+fn integer_root<N: Number>(number: &N, n: u32) -> Option<N> {
+    let (mut low, mut high) = (N::from_u32(0), number.clone());
+
+    // The highest root whose power doesn't go over the number
+    while low < high {
+        let (half, odd) = high.checked_sub(&low)?.checked_div_rem(&N::from_u32(2))?;
+        let middle = low.checked_add(&half)?.checked_add(&odd)?;
+
+        if checked_pow(&middle, n, Some(number)).is_some() {
+            low = middle;
+        } else {
+            high = middle.checked_sub(&N::from_u32(1))?;
+        }
+    }
+
+    (checked_pow(&low, n, None).as_ref() == Some(number)).then_some(low)
+}
+
+/// The power of ten that shifts a number left far enough to append `number`
+fn concatenation_shift<N: Number>(number: &N) -> Option<N> {
+    let ten = N::from_u32(10);
+    let mut shift = ten.clone();
+
+    while shift <= *number {
+        shift = shift.checked_mul(&ten)?;
+    }
+
+    Some(shift)
+}
+
+impl Operator {
+    /// `None` when the result doesn't fit or isn't defined, e.g. dividing by zero
+    fn apply<N: Number>(self, left: &N, right: &N) -> Option<N> {
+        match self {
+            Plus => left.checked_add(right),
+            Mul => left.checked_mul(right),
+            Concatenate => left
+                .checked_mul(&concatenation_shift(right)?)?
+                .checked_add(right),
+            Minus => left.checked_sub(right),
+            Div => Some(left.checked_div_rem(right)?.0),
+            Pow => checked_pow(left, right.to_u32()?, None),
+        }
+    }
+
+    /// The left operands that make `left self right` equal `result`, `None` when there are none
+    fn undo<N: Number>(self, result: &N, right: &N) -> Option<Lefts<N>> {
+        let (zero, one) = (N::from_u32(0), N::from_u32(1));
+
+        let left = match self {
+            Plus => result.checked_sub(right)?,
+            Mul if *right == zero => return (*result == zero).then_some(Lefts::Any),
+            Mul => match result.checked_div_rem(right)? {
+                (left, remainder) if remainder == zero => left,
+                _ => return None,
+            },
+            Concatenate => {
+                let left = result.checked_sub(right)?;
+
+                // Zero can be put in front of anything, even when the shift doesn't fit
+                if left == zero {
+                    return Some(Lefts::single(zero));
+                }
+
+                match left.checked_div_rem(&concatenation_shift(right)?)? {
+                    (left, remainder) if remainder == zero => left,
+                    _ => return None,
+                }
+            }
+            Minus => result.checked_add(right)?,
+            // Rounding down means every left operand up to the next multiple works
+            Div => {
+                return (*right != zero).then_some(Lefts::Consecutive {
+                    first: result.checked_mul(right)?,
+                    count: right.clone(),
+                });
+            }
+            Pow if *right == zero => return (*result == one).then_some(Lefts::Any),
+            Pow => integer_root(result, right.to_u32()?)?,
+        };
+
+        Some(Lefts::single(left))
+    }
+}
+
+/// Works backwards from the answer, undoing the last operator first
+///
+/// Most operators can only be undone for a few answers, e.g. `*` only when the answer is a
+/// multiple of the last number, so whole branches are dropped without evaluating them.
 pub struct Solver {
-    enum_combination_cache: HashMap<usize, Vec<Vec<Operator>>>,
+    operators: Vec<Operator>,
 }
 
 impl Solver {
-    fn new() -> Self {
+    pub fn new(operators: &[Operator]) -> Self {
         Self {
-            enum_combination_cache: HashMap::new(),
+            operators: operators.to_vec(),
         }
     }
 
-    fn is_line_correct(&mut self, line: &(usize, Vec<usize>)) -> bool {
+    pub fn is_line_correct<N: Number>(&self, line: &(N, Vec<N>)) -> bool {
+        self.witness(line).is_some()
+    }
+
+    /// Operators that make the answer, in the order they go between the numbers
+    pub fn witness<N: Number>(&self, line: &(N, Vec<N>)) -> Option<Vec<Operator>> {
         let (answer, inputs) = line;
 
-        let combinations =
-            self.generate_enum_combinations(&[Plus, Mul, Concatenate], inputs.len() - 1);
+        self.find_operators(answer, inputs)
+    }
 
-        combinations
-            .into_iter()
-            .find(|combination| Self::apply_operators(&inputs, &combination) == *answer)
-            .is_some()
+    /// How many different operator sequences make the answer
+    pub fn count_solutions<N: Number>(&self, line: &(N, Vec<N>)) -> usize {
+        let (answer, inputs) = line;
+
+        self.count_operators(answer, inputs, &mut HashMap::new())
     }
 
-    fn generate_enum_combinations(&mut self, variants: &[Operator], n: usize) -> &[Vec<Operator>] {
-        if self.enum_combination_cache.get(&n).is_none() {
-            let mut combinations = Vec::new();
-            let total_combinations = variants.len().pow(n as u32); // Total combinations = len(variants)^n
+    fn find_operators<N: Number>(&self, answer: &N, inputs: &[N]) -> Option<Vec<Operator>> {
+        let (last, rest) = inputs.split_last()?;
 
-            for i in 0..total_combinations {
-                let mut combination = Vec::new();
-                let mut value = i;
+        if rest.is_empty() {
+            return (answer == last).then(Vec::new);
+        }
 
-                for _ in 0..n {
-                    combination.push(variants[value % variants.len()].clone());
-                    value /= variants.len();
+        self.operators.iter().find_map(|&operator| {
+            let mut operators = match operator.undo(answer, last)? {
+                Lefts::Consecutive { first, count } if self.fewer_values(rest, &count) => self
+                    .values(rest)
+                    .range(&first..)
+                    .take_while(|&(value, _)| in_range(value, &first, &count))
+                    .map(|(_, (_, operators))| operators.clone())
+                    .next()?,
+                Lefts::Consecutive { first, count } => {
+                    consecutive(first, count).find_map(|left| self.find_operators(&left, rest))?
                 }
+                Lefts::Any => self.evaluate_operators(&inputs[0], &rest[1..])?,
+            };
 
-                combinations.push(combination);
-            }
+            operators.push(operator);
+            Some(operators)
+        })
+    }
+
+    /// Operators that make anything at all from the numbers, only subtraction, division and
+    /// numbers that don't fit can fail
+    fn evaluate_operators<N: Number>(&self, left: &N, inputs: &[N]) -> Option<Vec<Operator>> {
+        let Some((right, rest)) = inputs.split_first() else {
+            return Some(Vec::new());
+        };
+
+        self.operators.iter().find_map(|&operator| {
+            let result = operator.apply(left, right)?;
+            let mut operators = self.evaluate_operators(&result, rest)?;
+
+            operators.insert(0, operator);
+            Some(operators)
+        })
+    }
 
-            self.enum_combination_cache.insert(n, combinations);
+    /// Remembers the count per answer and number of inputs, the same pair is often reached in
+    /// several ways
+    fn count_operators<N: Number>(
+        &self,
+        answer: &N,
+        inputs: &[N],
+        counts: &mut HashMap<(N, usize), usize>,
+    ) -> usize {
+        let Some((last, rest)) = inputs.split_last() else {
+            return 0;
+        };
+
+        if rest.is_empty() {
+            return usize::from(answer == last);
+        }
+
+        if let Some(&count) = counts.get(&(answer.clone(), inputs.len())) {
+            return count;
         }
 
-        &self.enum_combination_cache.get(&n).unwrap()
+        let count = self
+            .operators
+            .iter()
+            .map(|operator| match operator.undo(answer, last) {
+                None => 0,
+                Some(Lefts::Consecutive { first, count }) if self.fewer_values(rest, &count) => {
+                    self.values(rest)
+                        .range(&first..)
+                        .take_while(|&(value, _)| in_range(value, &first, &count))
+                        .map(|(_, (count, _))| *count)
+                        .fold(0, usize::saturating_add)
+                }
+                Some(Lefts::Consecutive { first, count }) => consecutive(first, count)
+                    .map(|left| self.count_operators(&left, rest, counts))
+                    .fold(0, usize::saturating_add),
+                Some(Lefts::Any) => {
+                    self.count_evaluations(&inputs[0], &rest[1..], &mut HashMap::new())
+                }
+            })
+            .fold(0, usize::saturating_add);
+
+        counts.insert((answer.clone(), inputs.len()), count);
+        count
     }
 
-    fn apply_operators(inputs: &[usize], operators: &[Operator]) -> usize {
-        let mut inputs = inputs.to_owned();
-        for i in 0..inputs.len() - 1 {
-            let operator = operators.get(i).unwrap();
+    /// Whether evaluating `inputs` every way gives fewer values than there are in a range of
+    /// `count` numbers, e.g. when undoing a division by a large number
+    fn fewer_values<N: Number>(&self, inputs: &[N], count: &N) -> bool {
+        let exponent = u32::try_from(inputs.len() - 1).unwrap_or(u32::MAX);
 
-            let first = *inputs.get(i).unwrap();
-            let mut second = inputs.get_mut(i + 1).unwrap();
+        (self.operators.len() as u32)
+            .checked_pow(exponent)
+            .is_some_and(|values| N::from_u32(values) < *count)
+    }
 
-            *second = match operator {
-                Plus => first + *second,
-                Mul => first * *second,
-                Concatenate => format!("{}{}", first.to_string(), second.to_string())
-                    .parse::<usize>()
-                    .unwrap(),
-            };
+    /// Every value the numbers make, with how many operator sequences make it and one of them
+    fn values<N: Number>(&self, inputs: &[N]) -> BTreeMap<N, (usize, Vec<Operator>)> {
+        let mut values = BTreeMap::from([(inputs[0].clone(), (1, Vec::new()))]);
+
+        for right in &inputs[1..] {
+            let mut next: BTreeMap<N, (usize, Vec<Operator>)> = BTreeMap::new();
+
+            for (left, (count, operators)) in &values {
+                for &operator in &self.operators {
+                    let Some(result) = operator.apply(left, right) else {
+                        continue;
+                    };
+
+                    let entry = next.entry(result).or_insert_with(|| {
+                        let mut operators = operators.clone();
+                        operators.push(operator);
+                        (0, operators)
+                    });
+                    entry.0 = entry.0.saturating_add(*count);
+                }
+            }
+
+            values = next;
+        }
+
+        values
+    }
+
+    /// How many operator sequences evaluate at all, remembered per left operand and number of
+    /// inputs left. That only tells the inputs apart while they share their last number, so
+    /// `evaluations` can't be reused for another slice.
+    fn count_evaluations<N: Number>(
+        &self,
+        left: &N,
+        inputs: &[N],
+        evaluations: &mut HashMap<(N, usize), usize>,
+    ) -> usize {
+        let Some((right, rest)) = inputs.split_first() else {
+            return 1;
+        };
+
+        if let Some(&count) = evaluations.get(&(left.clone(), inputs.len())) {
+            return count;
         }
 
-        *inputs.get(inputs.len() - 1).unwrap()
+        let count = self
+            .operators
+            .iter()
+            .filter_map(|operator| operator.apply(left, right))
+            .map(|result| self.count_evaluations(&result, rest, evaluations))
+            .fold(0, usize::saturating_add);
+
+        evaluations.insert((left.clone(), inputs.len()), count);
+        count
+    }
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    fn year(&self) -> u16 {
+        2024
+    }
+
+    fn day(&self) -> u8 {
+        7
+    }
+
+    fn title(&self) -> &'static str {
+        "Bridge Repair"
+    }
+
+    fn fixtures(&self) -> &'static [(&'static str, &'static str)] {
+        &[("example", include_str!("test_input.txt"))]
+    }
+
+    fn parse(&self, input: &str) -> Option<Result<(), ParseError>> {
+        Some(parse_equations::<usize>(input).map(drop))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, ParseError> {
+        Ok(calculate(input, PART_1_OPERATORS)?.into())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, ParseError> {
+        Ok(calculate(input, PART_2_OPERATORS)?.into())
+    }
+
+    fn render(&self, part: u8, input: &str) -> Option<Result<String, ParseError>> {
+        match part {
+            1 => Some(explain::<usize>(input, PART_1_OPERATORS)),
+            2 => Some(explain::<usize>(input, PART_2_OPERATORS)),
+            _ => None,
+        }
     }
 }
 
 #[cfg(test)]
 mod day7_tests {
-    use crate::questions_2024::day7::{calculate, Operator};
+    use crate::bigint::BigUint;
+    use crate::input::puzzle_input;
+    use crate::questions_2024::day7::{
+        calculate, calculate_with, concatenation_shift, consecutive, explain, integer_root,
+        Equation, Lefts, Operator, OperatorCountError, Solver, PART_1_OPERATORS, PART_2_OPERATORS,
+    };
 
     #[test]
     fn test_input_challenge_1() {
         let input = include_str!("test_input.txt");
-        let result = calculate(input);
+        let result = calculate(input, PART_1_OPERATORS).unwrap();
 
         assert_eq!(result, 3749);
     }
 
     #[test]
     fn input_challenge_1() {
-        let input = include_str!("input.txt");
-        let result = calculate(input);
+        let input = &puzzle_input(2024, 7);
+        let result = calculate(input, PART_1_OPERATORS).unwrap();
 
-        // 1459904816
-        // 25243429947 is te laag
         println!("{result}")
     }
+
+    #[test]
+    fn test_input_challenge_2() {
+        let input = include_str!("test_input.txt");
+        let result = calculate(input, PART_2_OPERATORS).unwrap();
+
+        assert_eq!(result, 11387);
+    }
+
+    fn single(left: u64) -> Option<Lefts<u64>> {
+        Some(Lefts::single(left))
+    }
+
+    #[test]
+    fn undoes_operators() {
+        assert_eq!(Operator::Plus.undo(&15, &6), single(9));
+        assert_eq!(Operator::Plus.undo(&5u64, &6), None);
+        assert_eq!(Operator::Mul.undo(&292, &4), single(73));
+        assert_eq!(Operator::Mul.undo(&292u64, &5), None);
+        assert_eq!(Operator::Mul.undo(&0u64, &0), Some(Lefts::Any));
+        assert_eq!(Operator::Concatenate.undo(&156, &6), single(15));
+        assert_eq!(Operator::Concatenate.undo(&156, &56), single(1));
+        assert_eq!(Operator::Concatenate.undo(&156u64, &7), None);
+        assert_eq!(Operator::Concatenate.undo(&1000, &0), single(100));
+        assert_eq!(Operator::Minus.undo(&5, &6), single(11));
+        assert_eq!(
+            Operator::Div.undo(&5u64, &3),
+            Some(Lefts::Consecutive {
+                first: 15,
+                count: 3
+            })
+        );
+        assert_eq!(Operator::Div.undo(&5u64, &0), None);
+        assert_eq!(Operator::Pow.undo(&343, &3), single(7));
+        assert_eq!(Operator::Pow.undo(&344u64, &3), None);
+        assert_eq!(Operator::Pow.undo(&1u64, &0), Some(Lefts::Any));
+        assert_eq!(concatenation_shift(&0u64), Some(10));
+        assert_eq!(concatenation_shift(&100u64), Some(1000));
+        assert_eq!(concatenation_shift(&u64::MAX), None);
+        assert_eq!(integer_root(&(1u64 << 62), 2), Some(1 << 31));
+        assert_eq!(integer_root(&u64::MAX, 2), None);
+        assert_eq!(
+            consecutive(u64::MAX - 1, 5).collect::<Vec<_>>(),
+            [u64::MAX - 1, u64::MAX]
+        );
+    }
+
+    #[test]
+    fn undoing_agrees_with_applying() {
+        let operators = [
+            Operator::Plus,
+            Operator::Mul,
+            Operator::Concatenate,
+            Operator::Minus,
+            Operator::Div,
+            Operator::Pow,
+        ];
+
+        for operator in operators {
+            for (left, right) in [(0u64, 0), (7, 0), (7, 1), (12, 3), (3, 12), (100, 7)] {
+                let Some(result) = operator.apply(&left, &right) else {
+                    continue;
+                };
+
+                let undone = match operator.undo(&result, &right) {
+                    Some(Lefts::Consecutive { first, count }) => {
+                        consecutive(first, count).any(|undone| undone == left)
+                    }
+                    Some(Lefts::Any) => true,
+                    None => false,
+                };
+                assert!(undone, "{left} {operator:?} {right} = {result}");
+            }
+        }
+    }
+
+    #[test]
+    fn solves_with_operator_sets() {
+        let solver = Solver::new(PART_2_OPERATORS);
+
+        // 3^59 operator combinations, far too many to try them all
+        assert!(solver.is_line_correct(&(60u64, vec![1; 60])));
+        assert!(solver.is_line_correct(&(7290u64, vec![6, 8, 6, 15])));
+        assert!(solver.is_line_correct(&(0u64, vec![7, 3, 0])));
+        assert!(!solver.is_line_correct(&(161011u64, vec![16, 10, 13])));
+        assert!(!Solver::new(PART_1_OPERATORS).is_line_correct(&(7290u64, vec![6, 8, 6, 15])));
+
+        let solver = Solver::new(&[Operator::Minus, Operator::Div, Operator::Pow]);
+        assert!(solver.is_line_correct(&(9u64, vec![20, 3, 5, 2])));
+        assert!(solver.is_line_correct(&(1u64, vec![2, 5, 0])));
+
+        // Anything to the power 0 is 1, but 2 - 5 goes below zero and 2 ^ 5000000000 is too big
+        let solver = Solver::new(&[Operator::Minus, Operator::Pow]);
+        assert!(solver.is_line_correct(&(1u64, vec![2, 5, 0])));
+        assert!(!solver.is_line_correct(&(1u64, vec![2, 5_000_000_000, 0])));
+    }
+
+    #[test]
+    fn undoes_division_by_large_numbers() {
+        let solver = Solver::new(&[Operator::Div]);
+
+        // Any left operand from 0 up to 2^40 works, too many to try one by one
+        assert!(!solver.is_line_correct(&(0u64, vec![u64::MAX, 1 << 40])));
+        assert!(solver.is_line_correct(&(0u64, vec![12345, 1 << 40])));
+        assert!(solver.is_line_correct(&(4u64, vec![1 << 42, 1 << 40])));
+
+        let operators = [Operator::Plus, Operator::Mul, Operator::Div];
+        let solver = Solver::new(&operators);
+        let line = (1u64, vec![3, 4, 1 << 40, 1 << 40]);
+
+        let evaluate = |sequence: &[Operator]| {
+            sequence
+                .iter()
+                .zip(&line.1[1..])
+                .try_fold(line.1[0], |left, (operator, right)| {
+                    operator.apply(&left, right)
+                })
+        };
+
+        assert_eq!(evaluate(&solver.witness(&line).unwrap()), Some(1));
+
+        let every_sequence =
+            (0..27).map(|index: usize| [1, 3, 9].map(|digit| operators[index / digit % 3]));
+        let expected = every_sequence
+            .filter(|sequence| evaluate(sequence) == Some(1))
+            .count();
+        assert_eq!(solver.count_solutions(&line), expected);
+        assert_eq!(
+            solver.count_solutions(&(0u64, vec![3, 4, 1 << 40])),
+            solver.count_solutions(&(0u128, vec![3, 4, 1 << 40]))
+        );
+    }
+
+    #[test]
+    fn prunes_branches_that_overflow() {
+        let solver = Solver::new(&[Operator::Concatenate, Operator::Mul]);
+
+        // Whatever is multiplied by 0 has to be made first, which only fits in a u128
+        assert!(!solver.is_line_correct(&(0u64, vec![u64::MAX, 99, 0])));
+        assert!(solver.is_line_correct(&(0u128, vec![u64::MAX.into(), 99, 0])));
+
+        let solver = Solver::new(PART_2_OPERATORS);
+        assert!(!solver.is_line_correct(&(u64::MAX, vec![u64::MAX / 2 + 1, 2])));
+        assert!(solver.is_line_correct(&(u64::MAX - 1, vec![u64::MAX / 2, 2])));
+    }
+
+    #[test]
+    fn solves_with_any_backend() {
+        let input = include_str!("test_input.txt");
+
+        assert_eq!(
+            calculate_with::<u64>(input, PART_2_OPERATORS).unwrap(),
+            11387
+        );
+        assert_eq!(
+            calculate_with::<u128>(input, PART_2_OPERATORS).unwrap(),
+            11387
+        );
+        assert_eq!(
+            calculate_with::<BigUint>(input, PART_2_OPERATORS)
+                .unwrap()
+                .to_string(),
+            "11387"
+        );
+
+        let input = "123456789012345678901234567890: 1234567890 1234567890 1234567890\n";
+
+        let error = calculate_with::<u64>(input, PART_2_OPERATORS).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 1: expected integer, found '123456789012345678901234567890'"
+        );
+
+        let sum = calculate_with::<BigUint>(input, PART_2_OPERATORS).unwrap();
+        assert_eq!(sum.to_string(), "123456789012345678901234567890");
+
+        let input = "18446744073709551615: 18446744073709551615\n1: 1\n";
+
+        let error = calculate_with::<u64>(input, PART_2_OPERATORS).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 1: sum doesn't fit, found '1: 1'"
+        );
+        assert_eq!(
+            calculate_with::<u128>(input, PART_2_OPERATORS).unwrap(),
+            1 << 64
+        );
+    }
+
+    #[test]
+    fn finds_and_counts_operators() {
+        let solver = Solver::new(PART_2_OPERATORS);
+        let line = (3267u64, vec![81, 40, 27]);
+
+        let operators = solver.witness(&line).unwrap();
+        assert_eq!(operators.len(), 2);
+        assert_eq!(
+            operators
+                .iter()
+                .zip(&line.1[1..])
+                .try_fold(line.1[0], |left, (operator, right)| operator
+                    .apply(&left, right)),
+            Some(3267)
+        );
+        assert_eq!(solver.count_solutions(&line), 2);
+        assert_eq!(solver.witness(&(161011u64, vec![16, 10, 13])), None);
+        assert_eq!(solver.count_solutions(&(161011u64, vec![16, 10, 13])), 0);
+
+        // Every one of the 2^59 sequences of * and ^ makes 1, counted without trying them all
+        let solver = Solver::new(&[Operator::Mul, Operator::Pow]);
+        assert_eq!(solver.count_solutions(&(1u64, vec![1; 60])), 1 << 59);
+
+        let solver = Solver::new(PART_1_OPERATORS);
+        assert_eq!(solver.count_solutions(&(0u64, vec![5, 3, 0])), 2);
+        assert_eq!(
+            solver.witness(&(0u64, vec![5, 3, 0])),
+            Some(vec![Operator::Plus, Operator::Mul])
+        );
+
+        // Anything times the last zero works, so every sequence before it is counted
+        let mut inputs = vec![1; 60];
+        inputs.push(0);
+        assert_eq!(solver.count_solutions(&(0u64, inputs)), 1 << 59);
+    }
+
+    /// Every operator sequence tried one by one
+    fn count_by_trying(operators: &[Operator], answer: u64, inputs: &[u64]) -> usize {
+        let slots = inputs.len() as u32 - 1;
+
+        (0..operators.len().pow(slots))
+            .filter(|sequence| {
+                (0..slots as usize)
+                    .try_fold(inputs[0], |left, slot| {
+                        let operator = operators
+                            [sequence / operators.len().pow(slot as u32) % operators.len()];
+                        operator.apply(&left, &inputs[slot + 1])
+                    })
+                    .is_some_and(|result| result == answer)
+            })
+            .count()
+    }
+
+    #[test]
+    fn counts_like_trying_every_sequence() {
+        use Operator::{Div, Minus, Mul, Plus, Pow};
+
+        let operator_sets: [&[Operator]; 4] = [
+            &[Minus, Mul],
+            &[Minus, Pow],
+            &[Div, Mul, Plus],
+            &[Minus, Div, Pow],
+        ];
+
+        assert_eq!(
+            Solver::new(&[Minus, Mul]).count_solutions(&(0u64, vec![0, 1, 0, 0])),
+            4
+        );
+        assert_eq!(
+            Solver::new(&[Minus, Pow]).count_solutions(&(1u64, vec![1, 3, 1, 0, 0, 0])),
+            15
+        );
+
+        for operators in operator_sets {
+            let solver = Solver::new(operators);
+
+            for seed in 0..3u64.pow(6) {
+                let inputs: Vec<u64> = (0..6).map(|digit| seed / 3u64.pow(digit) % 3).collect();
+
+                for answer in 0..4 {
+                    assert_eq!(
+                        solver.count_solutions(&(answer, inputs.clone())),
+                        count_by_trying(operators, answer, &inputs),
+                        "{operators:?} {answer}: {inputs:?}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn explains_equations() {
+        let operators = [Operator::Mul];
+        assert_eq!(
+            Equation::new(&190, &[10, 19], &operators)
+                .unwrap()
+                .to_string(),
+            "190 = 10 * 19"
+        );
+        assert_eq!(
+            Equation::new(&190, &[10, 19, 1], &operators).err(),
+            Some(OperatorCountError {
+                inputs: 3,
+                operators: 1
+            })
+        );
+
+        let input = include_str!("test_input.txt");
+        let explanation = explain::<u64>(input, PART_2_OPERATORS).unwrap();
+        let lines: Vec<&str> = explanation.lines().collect();
+
+        assert_eq!(lines[0], "190 = 10 * 19 (1 way)");
+        assert_eq!(lines[3], "156 = 15 || 6 (1 way)");
+        assert_eq!(lines[5], "161011: 16 10 13 can't be made");
+        assert_eq!(lines[8], "292 = 11 + 6 * 16 + 20 (1 way)");
+    }
 }
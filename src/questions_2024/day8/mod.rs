@@ -1,17 +1,10 @@
+use crate::error::{ParseError, ParseErrorKind};
+use crate::geometry::Point;
+use crate::grid::Grid;
+use crate::solution::{Answer, Solution};
 use std::collections::HashSet;
 
-#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
-struct Coord {
-    x: i32,
-    y: i32,
-}
-
-fn calculate_overshoot_points(
-    a: &Coord,
-    b: &Coord,
-    grid: &[Vec<char>],
-    part_2: bool,
-) -> Vec<Coord> {
+fn calculate_overshoot_points(a: &Point, b: &Point, grid: &Grid<char>, part_2: bool) -> Vec<Point> {
     if !part_2 {
         return vec![
             calculate_overshoot_point(a, b),
@@ -19,36 +12,23 @@ fn calculate_overshoot_points(
         ];
     }
 
-    let width = grid.len();
-    let height = grid.get(0).unwrap().len();
-
-    let mut l_to_r = calculate_overshoot_point_continuous(a, b, width as i32, height as i32);
-    let r_to_l = calculate_overshoot_point_continuous(b, a, width as i32, height as i32);
+    let mut l_to_r = calculate_overshoot_point_continuous(a, b, grid);
+    let r_to_l = calculate_overshoot_point_continuous(b, a, grid);
 
     l_to_r.extend(r_to_l);
 
     l_to_r
 }
 
-fn calculate_overshoot_point(a: &Coord, b: &Coord) -> Coord {
-    let delta_x = b.x - a.x;
-    let delta_y = b.y - a.y;
-
-    Coord {
-        x: b.x + delta_x,
-        y: b.y + delta_y,
-    }
+fn calculate_overshoot_point(a: &Point, b: &Point) -> Point {
+    *b + (*b - *a)
 }
-fn calculate_overshoot_point_continuous(
-    a: &Coord,
-    b: &Coord,
-    width: i32,
-    height: i32,
-) -> Vec<Coord> {
+
+fn calculate_overshoot_point_continuous(a: &Point, b: &Point, grid: &Grid<char>) -> Vec<Point> {
     let mut result = Vec::new();
 
-    result.push(a.clone());
-    result.push(b.clone());
+    result.push(*a);
+    result.push(*b);
 
     loop {
         let next = calculate_overshoot_point(
@@ -56,7 +36,7 @@ fn calculate_overshoot_point_continuous(
             result.get(result.len() - 1).unwrap(),
         );
 
-        if next.y >= 0 && next.x >= 0 && next.x < width && next.y < height {
+        if grid.contains(next) {
             result.push(next);
         } else {
             break;
@@ -66,38 +46,38 @@ fn calculate_overshoot_point_continuous(
     result
 }
 
-fn find_all_char_positions(chr: char, grid: &[Vec<char>]) -> Vec<Coord> {
-    let mut result: Vec<Coord> = Vec::new();
+fn find_all_char_positions(chr: char, grid: &Grid<char>) -> Vec<Point> {
+    grid.iter()
+        .filter(|(_, c)| **c == chr)
+        .map(|(point, _)| point)
+        .collect()
+}
 
-    for y in 0..grid.len() {
-        for x in 0..grid.get(0).unwrap().len() {
-            if *grid.get(y).unwrap().get(x).unwrap() == chr {
-                result.push(Coord {
-                    x: x as i32,
-                    y: y as i32,
-                })
-            }
-        }
+fn parse_map(input: &str) -> Result<Grid<char>, ParseError> {
+    let grid = Grid::parse(input, Ok)?;
+
+    if grid.is_empty() {
+        return Err(ParseError::new(
+            input,
+            &input[input.len()..],
+            ParseErrorKind::MissingField("map"),
+        ));
     }
 
-    result
+    Ok(grid)
 }
 
-fn calculate(input: &str, part_2: bool) -> usize {
-    let grid: Vec<Vec<char>> = input
-        .split("\n")
-        .filter(|line| !line.is_empty())
-        .map(|line| line.chars().collect())
-        .collect();
+pub fn calculate(input: &str, part_2: bool) -> Result<usize, ParseError> {
+    let grid = parse_map(input)?;
 
-    let grid_width = grid.get(0).unwrap().iter().len();
-    let grid_height = grid.len();
+    let grid_width = grid.width();
+    let grid_height = grid.height();
 
-    let mut combinations: Vec<(Coord, Coord)> = Vec::new();
+    let mut combinations: Vec<(Point, Point)> = Vec::new();
 
     for y in 0..grid_height {
         for x in 0..grid_width {
-            let chr = *grid.get(y).unwrap().get(x).unwrap();
+            let chr = grid[(x, y)];
 
             if chr != '.' {
                 let coords = find_all_char_positions(chr, &grid);
@@ -105,7 +85,7 @@ fn calculate(input: &str, part_2: bool) -> usize {
                 for coord in coords.clone() {
                     for other_coord in &coords {
                         if coord != *other_coord {
-                            combinations.push((coord, other_coord.clone()));
+                            combinations.push((coord, *other_coord));
                         }
                     }
                 }
@@ -113,7 +93,7 @@ fn calculate(input: &str, part_2: bool) -> usize {
         }
     }
 
-    let mut coords: HashSet<Coord> = HashSet::new();
+    let mut coords: HashSet<Point> = HashSet::new();
 
     combinations.into_iter().for_each(|(a, b)| {
         let points = calculate_overshoot_points(&a, &b, &grid, part_2);
@@ -123,60 +103,84 @@ fn calculate(input: &str, part_2: bool) -> usize {
         });
     });
 
-    coords = coords
-        .into_iter()
-        .filter(|coord| {
-            coord.x >= 0
-                && coord.y >= 0
-                && coord.x < grid_width as i32
-                && coord.y < grid_height as i32
-        })
-        .collect();
+    coords.retain(|coord| grid.contains(*coord));
+
+    Ok(coords.len())
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    fn year(&self) -> u16 {
+        2024
+    }
 
-    println!("{:?}", coords);
+    fn day(&self) -> u8 {
+        8
+    }
 
-    coords.len()
+    fn title(&self) -> &'static str {
+        "Resonant Collinearity"
+    }
+
+    fn fixtures(&self) -> &'static [(&'static str, &'static str)] {
+        &[("example", include_str!("test_input.txt"))]
+    }
+
+    fn parse(&self, input: &str) -> Option<Result<(), ParseError>> {
+        Some(parse_map(input).map(drop))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, ParseError> {
+        Ok(calculate(input, false)?.into())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, ParseError> {
+        Ok(calculate(input, true)?.into())
+    }
 }
 
 #[cfg(test)]
 mod day8_tests {
-    use crate::questions_2024::day8::{calculate, Coord};
+    use crate::geometry::Point;
+    use crate::input::puzzle_input;
+    use crate::questions_2024::day8::calculate;
 
     #[test]
     fn test_input_challenge_1() {
-        let a = Coord { x: 4, y: 3 };
-        let b = Coord { x: 5, y: 5 };
+        let a = Point::new(4, 3);
+        let b = Point::new(5, 5);
 
         let input = include_str!("test_input.txt");
 
-        assert_eq!(calculate(input, false), 14);
+        assert_eq!(calculate(input, false), Ok(14));
     }
 
     #[test]
     fn test_input_challenge_2() {
-        let a = Coord { x: 4, y: 3 };
-        let b = Coord { x: 5, y: 5 };
+        let a = Point::new(4, 3);
+        let b = Point::new(5, 5);
 
         let input = include_str!("test_input.txt");
 
-        assert_eq!(calculate(input, true), 34);
+        assert_eq!(calculate(input, true), Ok(34));
     }
 
     #[test]
     fn input_challenge_1() {
-        let input = include_str!("input.txt");
+        let input = &puzzle_input(2024, 8);
 
         // 483 te hoog
         // 450 ook zelfs
-        println!("{}", calculate(input, false));
+        println!("{}", calculate(input, false).unwrap());
     }
 
     #[test]
     fn input_challenge_2() {
-        let input = include_str!("input.txt");
+        let input = &puzzle_input(2024, 8);
 
         // 483 te hoog
         // 450 ook zelfs
-        println!("{}", calculate(input, true));
+        println!("{}", calculate(input, true).unwrap());
     }
 }
@@ -1,155 +1,190 @@
+use crate::error::{ParseError, ParseErrorKind};
+use crate::solution::{Answer, Solution};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
 use std::fmt::{Debug, Formatter};
-use std::ops::Index;
 
-#[derive(Copy, Clone, Eq, PartialEq)]
-enum Type {
-    File(usize),
-    Empty,
+/// Every length in the disk map is a single digit
+const MAX_SPAN_LEN: usize = 9;
+
+/// A run of blocks on the disk, `id` is `None` for free space
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+struct Span {
+    id: Option<usize>,
+    start: usize,
+    len: usize,
 }
 
-impl Debug for Type {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let chr = match self {
-            Type::File(id) => &id.to_string(),
-            Type::Empty => ".",
-        };
+impl Span {
+    fn end(&self) -> usize {
+        self.start + self.len
+    }
+
+    /// Sum of the positions times the file id, without going over the blocks one by one
+    fn checksum(&self) -> usize {
+        let positions = self.len * self.start + self.len * self.len.saturating_sub(1) / 2;
 
-        f.write_str(&format!("{chr}"))
+        self.id.map_or(0, |id| id * positions)
     }
 }
 
+/// Files and the gaps between them, both ordered by where they start
 struct Disk {
-    data: Vec<Type>,
+    files: Vec<Span>,
+    gaps: Vec<Span>,
 }
 
+/// Draws every block like the puzzle does, e.g. `00...111`
 impl Debug for Disk {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        self.data
-            .iter()
-            .for_each(|t| f.write_str(&format!("{:?}", t)).unwrap());
+        let spans = self.files.iter().chain(&self.gaps);
+        let mut blocks = vec![None; spans.clone().map(Span::end).max().unwrap_or(0)];
+
+        for span in spans {
+            blocks[span.start..span.end()].fill(span.id);
+        }
+
+        for block in blocks {
+            match block {
+                Some(id) => write!(f, "{id}")?,
+                None => f.write_str(".")?,
+            }
+        }
 
         Ok(())
     }
 }
 
 impl Disk {
-    fn new(input: &str) -> Self {
-        let data: Vec<Type> = input
-            .split("\n")
-            .collect::<Vec<&str>>()
-            .get(0)
-            .map(|line| {
-                let mut is_odd = true;
-                let mut file_id = 0;
-                line.chars()
-                    .map(|chr| {
-                        is_odd = !is_odd;
-
-                        let parsed: usize = chr.to_string().parse().unwrap();
-
-                        if !is_odd {
-                            let result = Type::File(file_id);
-                            file_id += 1;
-                            return vec![result; parsed];
-                        } else {
-                            return vec![Type::Empty; parsed];
-                        }
-                    })
-                    .flatten()
-                    .collect()
-            })
-            .unwrap();
+    fn new(input: &str) -> Result<Self, ParseError> {
+        let line = input.split("\n").next().unwrap();
+
+        let mut files = Vec::new();
+        let mut gaps = Vec::new();
+        let mut start = 0;
+
+        for (index, chr) in line.char_indices() {
+            let len = chr.to_digit(10).ok_or_else(|| {
+                ParseError::new(
+                    input,
+                    &line[index..index + chr.len_utf8()],
+                    ParseErrorKind::ExpectedInteger,
+                )
+            })? as usize;
+
+            // The map alternates between files and free space, starting with a file
+            if files.len() == gaps.len() {
+                files.push(Span {
+                    id: Some(files.len()),
+                    start,
+                    len,
+                });
+            } else {
+                gaps.push(Span {
+                    id: None,
+                    start,
+                    len,
+                });
+            }
 
-        Self { data }
-    }
+            start += len;
+        }
+
+        gaps.retain(|gap| gap.len > 0);
 
-    fn get_first_free_space(&self, ending_position: usize, required_size: usize) -> Option<usize> {
-        self.data[0..ending_position]
-            .windows(required_size)
-            .position(|ts| {
-                if ts.len() != required_size {
-                    return false;
-                }
+        Ok(Self { files, gaps })
+    }
 
-                !ts.iter().any(|t| match t {
-                    Type::File(_) => true,
-                    Type::Empty => false,
-                })
+    /// The free space left between the files after they moved
+    fn update_gaps(&mut self) {
+        self.files.sort_by_key(|file| file.start);
+
+        self.gaps = self
+            .files
+            .windows(2)
+            .filter(|pair| pair[0].end() < pair[1].start)
+            .map(|pair| Span {
+                id: None,
+                start: pair[0].end(),
+                len: pair[1].start - pair[0].end(),
             })
+            .collect();
     }
 
+    /// Moves blocks one at a time from the end of the disk to the first free block, splitting
+    /// files over as many gaps as it takes
     fn compact_fragmented(&mut self) {
-        for i in (0..self.data.len()).rev() {
-            if let Type::File(id) = self.data.get(i).unwrap() {
-                let first_free_space = match self.get_first_free_space(i, 1) {
-                    None => return,
-                    Some(space) => space,
-                };
-                *self.data.get_mut(first_free_space).unwrap() = Type::File(*id);
-                *self.data.get_mut(i).unwrap() = Type::Empty;
+        let mut files = std::mem::take(&mut self.files);
+        let mut gaps = self.gaps.iter().copied();
+        let mut gap = gaps.next();
+
+        while let (Some(free), Some(last)) = (gap.as_mut(), files.last_mut()) {
+            if free.start >= last.start {
+                break;
+            }
+
+            let moved = free.len.min(last.len);
+            self.files.push(Span {
+                id: last.id,
+                start: free.start,
+                len: moved,
+            });
+
+            free.start += moved;
+            free.len -= moved;
+            last.len -= moved;
+
+            if last.len == 0 {
+                files.pop();
+            }
+            if free.len == 0 {
+                gap = gaps.next();
             }
         }
-    }
 
-    // This is garbage lol
-    fn get_file_size(&self, ending_position: usize) -> Option<usize> {
-        let file = match self.data.get(ending_position).unwrap() {
-            Type::File(index) => index,
-            Type::Empty => panic!(),
-        };
-
-        self.data[..ending_position]
-            .iter()
-            .rev()
-            .enumerate()
-            .find(|(index, t)| match t {
-                Type::File(other_file) => file != other_file,
-                Type::Empty => true,
-            })
-            .map(|(index, _)| index + 1)
+        self.files.extend(files);
+        self.update_gaps();
     }
 
+    /// Moves whole files to the first gap they fit in, highest id first
+    ///
+    /// Gaps are kept in one min-heap of start positions per length, so the first gap that fits
+    /// is the earliest of the heads of the heaps that are long enough.
     fn compact_defragmented(&mut self) {
-        let mut skip = 0; // The horrors....
-        for mut i in (0..self.data.len()).rev() {
-            i -= skip; // close your eyes
-
-            if let Type::File(id) = self.data.get(i).unwrap() {
-                let id = *id;
-                let file_size = match self.get_file_size(i) {
-                    None => return,
-                    Some(val) => val,
-                };
-
-                match self.get_first_free_space(i, file_size) {
-                    None => skip += file_size - 1,
-                    Some(space) => {
-                        for x in 0..file_size {
-                            *self.data.get_mut(space + x).unwrap() = Type::File(id);
-                            *self.data.get_mut(i - x).unwrap() = Type::Empty;
-                        }
-                    }
-                }
+        let mut free: Vec<BinaryHeap<Reverse<usize>>> = vec![BinaryHeap::new(); MAX_SPAN_LEN + 1];
+        for gap in &self.gaps {
+            free[gap.len].push(Reverse(gap.start));
+        }
+
+        for file in self.files.iter_mut().rev() {
+            // Only gaps before the file, moving right would make the disk less compact
+            let first_fit = (file.len.max(1)..=MAX_SPAN_LEN)
+                .filter_map(|len| free[len].peek().map(|&Reverse(start)| (start, len)))
+                .filter(|&(start, _)| start < file.start)
+                .min();
+
+            let Some((start, len)) = first_fit else {
+                continue;
+            };
+
+            free[len].pop();
+            if len > file.len {
+                free[len - file.len].push(Reverse(start + file.len));
             }
+
+            file.start = start;
         }
+
+        self.update_gaps();
     }
 
     fn calculate_checksum(&self) -> usize {
-        self.data
-            .iter()
-            .enumerate()
-            .filter_map(|(index, t)| match t {
-                Type::File(file_index) => Some(file_index * index),
-                Type::Empty => None,
-            })
-            .sum()
+        self.files.iter().map(Span::checksum).sum()
     }
 }
 
-fn calculate(input: &str, defragmented: bool) -> usize {
-    let mut disk = Disk::new(input);
-
-    println!("{:?}", disk);
+pub fn calculate(input: &str, defragmented: bool) -> Result<usize, ParseError> {
+    let mut disk = Disk::new(input)?;
 
     if defragmented {
         disk.compact_defragmented();
@@ -157,42 +192,95 @@ fn calculate(input: &str, defragmented: bool) -> usize {
         disk.compact_fragmented();
     }
 
-    println!("{:?}", disk);
+    Ok(disk.calculate_checksum())
+}
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    fn year(&self) -> u16 {
+        2024
+    }
+
+    fn day(&self) -> u8 {
+        9
+    }
+
+    fn title(&self) -> &'static str {
+        "Disk Fragmenter"
+    }
+
+    fn fixtures(&self) -> &'static [(&'static str, &'static str)] {
+        &[("example", include_str!("test_input.txt"))]
+    }
+
+    fn parse(&self, input: &str) -> Option<Result<(), ParseError>> {
+        Some(Disk::new(input).map(drop))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, ParseError> {
+        Ok(calculate(input, false)?.into())
+    }
 
-    disk.calculate_checksum()
+    fn part2(&self, input: &str) -> Result<Answer, ParseError> {
+        Ok(calculate(input, true)?.into())
+    }
 }
 
 #[cfg(test)]
 mod day9_tests {
-    use crate::questions_2024::day9::calculate;
+    use crate::input::puzzle_input;
+    use crate::questions_2024::day9::{calculate, Disk};
 
     #[test]
     fn test_input_challenge_1() {
         let input = include_str!("test_input.txt");
 
-        assert_eq!(calculate(input, false), 1928);
+        assert_eq!(calculate(input, false), Ok(1928));
     }
     #[test]
     fn test_input_challenge_2() {
         let input = include_str!("test_input.txt");
 
-        assert_eq!(calculate(input, true), 2858);
+        assert_eq!(calculate(input, true), Ok(2858));
     }
 
     #[test]
     fn input_challenge_1() {
-        let input = include_str!("input.txt");
+        let input = &puzzle_input(2024, 9);
 
         //6362722604045
         //6337921897505
-        println!("{}", calculate(input, false));
+        println!("{}", calculate(input, false).unwrap());
     }
 
     #[test]
     fn input_challenge_2() {
-        let input = include_str!("input.txt");
+        let input = &puzzle_input(2024, 9);
 
         //6337921897505
-        println!("{}", calculate(input, true));
+        println!("{}", calculate(input, true).unwrap());
+    }
+
+    #[test]
+    fn compacts_like_the_puzzle() {
+        let mut disk = Disk::new("12345\n").unwrap();
+        assert_eq!(format!("{disk:?}"), "0..111....22222");
+        disk.compact_fragmented();
+        assert_eq!(format!("{disk:?}"), "022111222");
+
+        let input = include_str!("test_input.txt");
+
+        let mut disk = Disk::new(input).unwrap();
+        disk.compact_fragmented();
+        assert_eq!(format!("{disk:?}"), "0099811188827773336446555566");
+
+        let mut disk = Disk::new(input).unwrap();
+        disk.compact_defragmented();
+        assert_eq!(
+            format!("{disk:?}"),
+            "00992111777.44.333....5555.6666.....8888"
+        );
+        assert_eq!(disk.gaps.len(), 5);
     }
 }
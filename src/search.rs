@@ -0,0 +1,287 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+/// The nodes one step away from `node`, with the cost of that step
+///
+/// Implemented for closures, so a graph can be as simple as `|node: &Point| ...`.
+pub trait Neighbours<N> {
+    fn neighbours(&self, node: &N) -> Vec<(N, usize)>;
+}
+
+impl<N, F, I> Neighbours<N> for F
+where
+    F: Fn(&N) -> I,
+    I: IntoIterator<Item = (N, usize)>,
+{
+    fn neighbours(&self, node: &N) -> Vec<(N, usize)> {
+        self(node).into_iter().collect()
+    }
+}
+
+/// A path found by one of the searches, `nodes` includes both the start and the goal
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Path<N> {
+    pub cost: usize,
+    pub nodes: Vec<N>,
+}
+
+/// Every node seen so far, with the index of the node it was reached from
+struct Visited<N> {
+    index: HashMap<N, usize>,
+    nodes: Vec<(N, Option<usize>, usize)>,
+}
+
+impl<N: Clone + Eq + Hash> Visited<N> {
+    fn new(start: N) -> Self {
+        Self {
+            index: HashMap::from([(start.clone(), 0)]),
+            nodes: vec![(start, None, 0)],
+        }
+    }
+
+    fn insert(&mut self, node: N, parent: usize, cost: usize) -> usize {
+        let id = self.nodes.len();
+        self.index.insert(node.clone(), id);
+        self.nodes.push((node, Some(parent), cost));
+
+        id
+    }
+
+    fn path(&self, mut id: usize) -> Path<N> {
+        let cost = self.nodes[id].2;
+        let mut nodes = vec![self.nodes[id].0.clone()];
+
+        while let Some(parent) = self.nodes[id].1 {
+            nodes.push(self.nodes[parent].0.clone());
+            id = parent;
+        }
+        nodes.reverse();
+
+        Path { cost, nodes }
+    }
+}
+
+/// Fewest steps to a goal, step costs are ignored and the cost is the number of steps
+pub fn bfs<N, G>(graph: &G, start: N, mut is_goal: impl FnMut(&N) -> bool) -> Option<Path<N>>
+where
+    N: Clone + Eq + Hash,
+    G: Neighbours<N> + ?Sized,
+{
+    let mut visited = Visited::new(start);
+    let mut queue = VecDeque::from([0]);
+
+    while let Some(id) = queue.pop_front() {
+        let (node, _, steps) = &visited.nodes[id];
+        if is_goal(node) {
+            return Some(visited.path(id));
+        }
+
+        let steps = steps + 1;
+        for (next, _) in graph.neighbours(node) {
+            if !visited.index.contains_key(&next) {
+                queue.push_back(visited.insert(next, id, steps));
+            }
+        }
+    }
+
+    None
+}
+
+/// Cheapest path to a goal
+pub fn dijkstra<N, G>(graph: &G, start: N, is_goal: impl FnMut(&N) -> bool) -> Option<Path<N>>
+where
+    N: Clone + Eq + Hash,
+    G: Neighbours<N> + ?Sized,
+{
+    astar(graph, start, is_goal, |_| 0)
+}
+
+/// Cheapest path to a goal, guided by `heuristic`
+///
+/// The path is only guaranteed to be the cheapest when the heuristic never overestimates the
+/// remaining cost.
+pub fn astar<N, G>(
+    graph: &G,
+    start: N,
+    mut is_goal: impl FnMut(&N) -> bool,
+    heuristic: impl Fn(&N) -> usize,
+) -> Option<Path<N>>
+where
+    N: Clone + Eq + Hash,
+    G: Neighbours<N> + ?Sized,
+{
+    let mut heap = BinaryHeap::from([Reverse((heuristic(&start), 0, 0))]);
+    let mut visited = Visited::new(start);
+
+    while let Some(Reverse((_, cost, id))) = heap.pop() {
+        let node = &visited.nodes[id].0;
+
+        // A cheaper way to this node was found after this entry was queued
+        if cost > visited.nodes[id].2 {
+            continue;
+        }
+
+        if is_goal(node) {
+            return Some(visited.path(id));
+        }
+
+        for (next, step_cost) in graph.neighbours(node) {
+            let next_cost = cost + step_cost;
+            let estimate = next_cost + heuristic(&next);
+
+            let next_id = match visited.index.get(&next) {
+                Some(&next_id) if visited.nodes[next_id].2 <= next_cost => continue,
+                Some(&next_id) => {
+                    visited.nodes[next_id].1 = Some(id);
+                    visited.nodes[next_id].2 = next_cost;
+                    next_id
+                }
+                None => visited.insert(next, id, next_cost),
+            };
+
+            heap.push(Reverse((estimate, next_cost, next_id)));
+        }
+    }
+
+    None
+}
+
+/// Most expensive path to a goal that doesn't visit any node twice
+///
+/// Tries every such path, so it's only feasible on small graphs or graphs with few branches.
+pub fn longest_path<N, G>(
+    graph: &G,
+    start: N,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N>>
+where
+    N: Clone + Eq + Hash,
+    G: Neighbours<N> + ?Sized,
+{
+    if is_goal(&start) {
+        return Some(Path {
+            cost: 0,
+            nodes: vec![start],
+        });
+    }
+
+    let mut longest: Option<Path<N>> = None;
+    let mut on_path = HashSet::from([start.clone()]);
+    let mut pending = vec![graph.neighbours(&start).into_iter()];
+    let mut path = vec![(start, 0)];
+
+    // Depth first without recursion, paths can be far longer than the stack allows
+    while let Some(next) = pending.last_mut() {
+        let Some((node, step_cost)) = next.next() else {
+            pending.pop();
+            on_path.remove(&path.pop().unwrap().0);
+            continue;
+        };
+
+        if on_path.contains(&node) {
+            continue;
+        }
+
+        let cost = path.last().unwrap().1 + step_cost;
+
+        if is_goal(&node) {
+            if longest.as_ref().is_none_or(|longest| cost > longest.cost) {
+                let mut nodes: Vec<N> = path.iter().map(|(node, _)| node.clone()).collect();
+                nodes.push(node);
+                longest = Some(Path { cost, nodes });
+            }
+            continue;
+        }
+
+        on_path.insert(node.clone());
+        pending.push(graph.neighbours(&node).into_iter());
+        path.push((node, cost));
+    }
+
+    longest
+}
+
+#[cfg(test)]
+mod search_tests {
+    use crate::geometry::Point;
+    use crate::grid::Grid;
+    use crate::search::{astar, bfs, dijkstra, longest_path, Path};
+
+    const MAZE: &str = "\
+S....#
+#.##.E
+#....#
+";
+
+    fn open_neighbours(grid: &Grid<char>) -> impl Fn(&Point) -> Vec<(Point, usize)> + '_ {
+        |point: &Point| {
+            grid.neighbours_4(*point)
+                .filter(|(_, tile)| **tile != '#')
+                .map(|(next, _)| (next, 1))
+                .collect()
+        }
+    }
+
+    #[test]
+    fn finds_shortest_paths() {
+        let grid = Grid::parse(MAZE, Ok).unwrap();
+        let (start, end) = (Point::ORIGIN, Point::new(5, 1));
+        let graph = open_neighbours(&grid);
+
+        let path = bfs(&graph, start, |point| *point == end).unwrap();
+        assert_eq!(path.cost, 6);
+        assert_eq!(path.nodes.len(), 7);
+        assert_eq!(path.nodes.first(), Some(&start));
+        assert_eq!(path.nodes.last(), Some(&end));
+
+        assert_eq!(
+            dijkstra(&graph, start, |point| *point == end),
+            Some(path.clone())
+        );
+        assert_eq!(
+            astar(
+                &graph,
+                start,
+                |point| *point == end,
+                |point| point.manhattan(end)
+            )
+            .map(|path| path.cost),
+            Some(6)
+        );
+        assert_eq!(bfs(&graph, start, |point| point.x > 5), None);
+    }
+
+    #[test]
+    fn dijkstra_weighs_steps() {
+        // Going straight to 3 costs 10, going around through 1 and 2 costs 3
+        let edges = |node: &u8| match node {
+            0 => vec![(1, 1), (3, 10)],
+            1 => vec![(2, 1)],
+            2 => vec![(3, 1)],
+            _ => vec![],
+        };
+
+        assert_eq!(
+            dijkstra(&edges, 0, |node| *node == 3),
+            Some(Path {
+                cost: 3,
+                nodes: vec![0, 1, 2, 3]
+            })
+        );
+        assert_eq!(bfs(&edges, 0, |node| *node == 3).unwrap().nodes, [0, 3]);
+    }
+
+    #[test]
+    fn finds_longest_simple_path() {
+        let grid = Grid::parse(MAZE, Ok).unwrap();
+        let end = Point::new(5, 1);
+
+        let path = longest_path(&open_neighbours(&grid), Point::ORIGIN, |point| {
+            *point == end
+        });
+
+        // The long way round the loop
+        assert_eq!(path.unwrap().cost, 8);
+    }
+}
@@ -0,0 +1,85 @@
+use crate::error::ParseError;
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Answer {
+    Number(usize),
+    Text(String),
+    Unsolved,
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Number(number) => write!(f, "{number}"),
+            Answer::Text(text) => f.write_str(text),
+            Answer::Unsolved => f.write_str("unsolved"),
+        }
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(number: usize) -> Self {
+        Answer::Number(number)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Answer::Text(text)
+    }
+}
+
+/// Another way of solving a part, benchmarked as its own stage so both can be compared
+pub struct Variant {
+    pub name: &'static str,
+    pub part: u8,
+    pub solve: fn(&str) -> Result<Answer, ParseError>,
+}
+
+/// Shared entry point for every puzzle, so tooling doesn't need to know how a day is solved
+pub trait Solution: Sync {
+    fn year(&self) -> u16;
+    fn day(&self) -> u8;
+    fn title(&self) -> &'static str;
+
+    /// Named example inputs from the puzzle text, e.g. `("example", ...)`
+    fn fixtures(&self) -> &'static [(&'static str, &'static str)] {
+        &[]
+    }
+
+    /// Only parses the input, `None` when parsing isn't a separate step of the solution
+    fn parse(&self, _input: &str) -> Option<Result<(), ParseError>> {
+        None
+    }
+
+    /// Draws how a part was solved, e.g. the path taken through a map, `None` when there's
+    /// nothing to draw
+    fn render(&self, _part: u8, _input: &str) -> Option<Result<String, ParseError>> {
+        None
+    }
+
+    /// One picture per step of a part, for animating it, `None` when there's nothing to animate
+    fn render_frames(&self, _part: u8, _input: &str) -> Option<Result<Vec<String>, ParseError>> {
+        None
+    }
+
+    /// Alternative implementations of the parts, e.g. a faster rewrite
+    fn variants(&self) -> &'static [Variant] {
+        &[]
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, ParseError>;
+    fn part2(&self, input: &str) -> Result<Answer, ParseError>;
+
+    /// Runs either part, parse errors are tagged with this puzzle's year and day
+    fn part(&self, part: u8, input: &str) -> Option<Result<Answer, ParseError>> {
+        let result = match part {
+            1 => self.part1(input),
+            2 => self.part2(input),
+            _ => return None,
+        };
+
+        Some(result.map_err(|err| err.in_puzzle(self.year(), self.day())))
+    }
+}